@@ -1,7 +1,17 @@
-use std::collections::VecDeque;
+use std::borrow::Cow;
+use std::collections::{HashSet, VecDeque};
 
+use serde::{Deserialize, Serialize};
+
+use crate::jobs::{Job, JobId};
+use crate::journal::Journal;
 use crate::mask::{MaskKind, ObjectMask};
-use crate::models::{BucketInfo, ObjectInfo, StorageClassTier};
+use crate::mask_library::MaskLibrary;
+use crate::models::{BucketInfo, ObjectInfo, RetrievalTier, StorageClassTier};
+use crate::pricing::PricingTable;
+use crate::scan::FullScan;
+use crate::settings::Settings;
+use crate::tracker::RestorePoll;
 
 const STATUS_LIMIT: usize = 20;
 
@@ -23,6 +33,10 @@ pub enum AppMode {
     ViewingRestoreRequests,
     CredentialError,
     ShowingProgress,
+    ViewingJobs,
+    ResumingJournals,
+    BrowsingMaskLibrary,
+    NamingSavedMask,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -36,6 +50,9 @@ pub enum MaskEditorField {
     Mode,
     Case,
     StorageClass,
+    Size,
+    Modified,
+    Tag,
 }
 
 impl MaskEditorField {
@@ -44,20 +61,81 @@ impl MaskEditorField {
             MaskEditorField::Pattern => MaskEditorField::Mode,
             MaskEditorField::Mode => MaskEditorField::Case,
             MaskEditorField::Case => MaskEditorField::StorageClass,
-            MaskEditorField::StorageClass => MaskEditorField::Pattern,
+            MaskEditorField::StorageClass => MaskEditorField::Size,
+            MaskEditorField::Size => MaskEditorField::Modified,
+            MaskEditorField::Modified => MaskEditorField::Tag,
+            MaskEditorField::Tag => MaskEditorField::Pattern,
         }
     }
 
     pub fn previous(self) -> Self {
         match self {
-            MaskEditorField::Pattern => MaskEditorField::StorageClass,
+            MaskEditorField::Pattern => MaskEditorField::Tag,
             MaskEditorField::Mode => MaskEditorField::Pattern,
             MaskEditorField::Case => MaskEditorField::Mode,
             MaskEditorField::StorageClass => MaskEditorField::Case,
+            MaskEditorField::Size => MaskEditorField::StorageClass,
+            MaskEditorField::Modified => MaskEditorField::Size,
+            MaskEditorField::Tag => MaskEditorField::Modified,
+        }
+    }
+
+    /// Fields edited as free text (with a cursor) rather than cycled
+    pub fn is_text(self) -> bool {
+        matches!(
+            self,
+            MaskEditorField::Pattern
+                | MaskEditorField::Size
+                | MaskEditorField::Modified
+                | MaskEditorField::Tag
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LibraryFormField {
+    #[default]
+    Name,
+    Description,
+    Bucket,
+}
+
+impl LibraryFormField {
+    pub fn next(self) -> Self {
+        match self {
+            LibraryFormField::Name => LibraryFormField::Description,
+            LibraryFormField::Description => LibraryFormField::Bucket,
+            LibraryFormField::Bucket => LibraryFormField::Name,
         }
     }
 }
 
+/// Name, description and bucket binding for a library entry being saved or renamed
+#[derive(Clone, Debug, Default)]
+pub struct LibraryForm {
+    /// Entry being renamed; None when saving the active mask as a new entry
+    pub editing: Option<usize>,
+    pub name: String,
+    pub description: String,
+    pub bucket: Option<String>,
+    pub field: LibraryFormField,
+}
+
+/// How many matching keys the mask editor lists under its live count
+pub const MASK_PREVIEW_KEYS: usize = 8;
+
+/// Live result of the mask being edited, evaluated against the loaded objects
+#[derive(Clone, Debug, Default)]
+pub struct MaskPreview {
+    pub matches: usize,
+    /// First matching keys, in listing order
+    pub keys: Vec<String>,
+    /// Objects passing every other filter whose tags haven't been fetched yet
+    pub untagged: usize,
+    /// Why the draft can't be evaluated (bad pattern or filter)
+    pub error: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MaskDraft {
     pub pattern: String,
@@ -65,9 +143,70 @@ pub struct MaskDraft {
     pub case_sensitive: bool,
     pub storage_class_filter: Option<StorageClassTier>,
     pub storage_class_cursor: usize,
+    /// Size predicate as typed, e.g. "> 1 GiB"; parsed when the mask is applied
+    pub size_input: String,
+    /// Modified-date predicate as typed, e.g. "> 90d" or "2023-01-01..2023-06-30"
+    pub modified_input: String,
+    /// Tag predicate as typed, e.g. "retention=short" or "project exists"
+    pub tag_input: String,
+    /// Folder scope carried over from the mask; set from folder view, not typed
+    pub scope: Option<String>,
+    /// Cursor within the focused text field, as a byte offset on a char boundary
     pub cursor_pos: usize,
 }
 
+impl MaskDraft {
+    /// Fill the editor from an existing mask, e.g. one loaded from the library
+    pub fn from_mask(mask: &ObjectMask) -> Self {
+        let storage_class_cursor = StorageClassTier::all_for_filter()
+            .iter()
+            .position(|(_, filter)| filter == &mask.storage_class_filter)
+            .unwrap_or(0);
+        Self {
+            pattern: mask.pattern.clone(),
+            kind: mask.kind.clone(),
+            case_sensitive: mask.case_sensitive,
+            storage_class_filter: mask.storage_class_filter.clone(),
+            storage_class_cursor,
+            size_input: mask
+                .size_filter
+                .map(|filter| filter.to_string())
+                .unwrap_or_default(),
+            modified_input: mask
+                .modified_filter
+                .map(|filter| filter.to_string())
+                .unwrap_or_default(),
+            tag_input: mask
+                .tag_filter
+                .as_ref()
+                .map(|filter| filter.to_string())
+                .unwrap_or_default(),
+            scope: mask.scope.clone(),
+            cursor_pos: mask.pattern.len(),
+        }
+    }
+
+    pub fn text_field(&self, field: MaskEditorField) -> Option<&String> {
+        match field {
+            MaskEditorField::Pattern => Some(&self.pattern),
+            MaskEditorField::Size => Some(&self.size_input),
+            MaskEditorField::Modified => Some(&self.modified_input),
+            MaskEditorField::Tag => Some(&self.tag_input),
+            _ => None,
+        }
+    }
+
+    pub fn text_field_mut(&mut self, field: MaskEditorField) -> Option<&mut String> {
+        match field {
+            MaskEditorField::Pattern => Some(&mut self.pattern),
+            MaskEditorField::Size => Some(&mut self.size_input),
+            MaskEditorField::Modified => Some(&mut self.modified_input),
+            MaskEditorField::Tag => Some(&mut self.tag_input),
+            _ => None,
+        }
+    }
+}
+
 impl Default for MaskDraft {
     fn default() -> Self {
         Self {
@@ -76,14 +215,26 @@ impl Default for MaskDraft {
             case_sensitive: false,
             storage_class_filter: None,
             storage_class_cursor: 0,
+            size_input: String::new(),
+            modified_input: String::new(),
+            tag_input: String::new(),
+            scope: None,
             cursor_pos: 0,
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PendingAction {
-    Transition { target_class: StorageClassTier },
-    Restore { days: i32 },
+    Transition {
+        target_class: StorageClassTier,
+    },
+    Restore {
+        days: i32,
+        // Journals written before the tier was selectable used the S3 default
+        #[serde(default)]
+        tier: RetrievalTier,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -91,6 +242,7 @@ pub struct ProgressState {
     pub operation: String,
     pub current: usize,
     pub total: usize,
+    pub in_flight: usize,
     pub current_item: Option<String>,
 }
 
@@ -100,15 +252,11 @@ impl ProgressState {
             operation,
             current: 0,
             total,
+            in_flight: 0,
             current_item: None,
         }
     }
 
-    pub fn update(&mut self, current: usize, item: Option<String>) {
-        self.current = current;
-        self.current_item = item;
-    }
-
     pub fn percentage(&self) -> u16 {
         if self.total == 0 {
             return 0;
@@ -139,10 +287,45 @@ pub struct App {
     pub pending_bucket_load: bool,
     // Pagination state
     pub total_object_count: Option<usize>,
+    pub listing_prefix: Option<String>,
     pub continuation_token: Option<String>,
     pub is_loading_objects: bool,
-    // Progress tracking
-    pub progress: Option<ProgressState>,
+    // Background jobs
+    pub jobs: Vec<Job>,
+    pub next_job_id: JobId,
+    pub focused_job: Option<JobId>,
+    pub job_cursor: usize,
+    // Bucket-wide target set for the pending action
+    pub target_scan: Option<FullScan>,
+    /// Marked rows or cursor row the confirm popup was opened for, copied so a
+    /// reload while it's open can't change what gets acted on
+    pub pending_targets: Option<Vec<ObjectInfo>>,
+    pub settings: Settings,
+    pub pricing: PricingTable,
+    /// Journals left behind by interrupted jobs, offered for resuming at startup
+    pub pending_journals: Vec<Journal>,
+    pub journal_cursor: usize,
+    // Background status checks of tracked restore requests
+    pub restore_poll: Option<RestorePoll>,
+    pub last_restore_poll: Option<std::time::Instant>,
+    /// Write confirmed actions to a plan file instead of calling S3
+    pub dry_run: bool,
+    pub mask_library: MaskLibrary,
+    pub library_cursor: usize,
+    pub library_form: LibraryForm,
+    /// Library entry whose mask is open in the mask editor
+    pub editing_saved_mask: Option<usize>,
+    pub mask_preview: MaskPreview,
+    /// Keys marked in the Objects pane; when any are marked, actions target only these
+    pub marked_keys: HashSet<String>,
+    /// Row a Shift range extends from (index into the Objects pane rows)
+    pub mark_anchor: Option<usize>,
+    /// List one folder at a time (delimiter listing) instead of every key
+    pub folder_view: bool,
+    /// Folder shown in folder view, "" for the bucket root, otherwise ending in `/`
+    pub folder: String,
+    /// Subfolders of `folder`, as full prefixes; listed above the objects
+    pub folders: Vec<String>,
 }
 
 impl App {
@@ -186,9 +369,32 @@ impl App {
             last_bucket_change: None,
             pending_bucket_load: false,
             total_object_count: None,
+            listing_prefix: None,
             continuation_token: None,
             is_loading_objects: false,
-            progress: None,
+            jobs: Vec::new(),
+            next_job_id: 1,
+            focused_job: None,
+            job_cursor: 0,
+            target_scan: None,
+            pending_targets: None,
+            settings: Settings::default(),
+            pricing: PricingTable::default(),
+            pending_journals: Vec::new(),
+            journal_cursor: 0,
+            restore_poll: None,
+            last_restore_poll: None,
+            dry_run: false,
+            mask_library: MaskLibrary::default(),
+            library_cursor: 0,
+            library_form: LibraryForm::default(),
+            editing_saved_mask: None,
+            mask_preview: MaskPreview::default(),
+            marked_keys: HashSet::new(),
+            mark_anchor: None,
+            folder_view: false,
+            folder: String::new(),
+            folders: Vec::new(),
         }
     }
 
@@ -198,8 +404,46 @@ impl App {
             .map(|b| b.name.as_str())
     }
 
+    pub fn job(&self, id: JobId) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    pub fn running_job_count(&self) -> usize {
+        self.jobs.iter().filter(|job| job.is_running()).count()
+    }
+
+    /// Object under the cursor; None on a folder row
     pub fn selected_object(&self) -> Option<&ObjectInfo> {
-        self.active_objects().get(self.selected_object)
+        let index = self.selected_object.checked_sub(self.folder_rows().len())?;
+        self.active_objects().get(index)
+    }
+
+    /// Folder under the cursor in folder view
+    pub fn selected_folder(&self) -> Option<&str> {
+        self.folder_rows()
+            .get(self.selected_object)
+            .map(String::as_str)
+    }
+
+    /// Subfolder rows shown above the objects, empty outside folder view
+    pub fn folder_rows(&self) -> &[String] {
+        if self.folder_view { &self.folders } else { &[] }
+    }
+
+    /// Rows in the Objects pane: folder rows, then objects
+    pub fn object_rows(&self) -> usize {
+        self.folder_rows().len() + self.active_objects().len()
+    }
+
+    /// Parent of the current folder, None at the bucket root
+    pub fn parent_folder(&self) -> Option<String> {
+        if self.folder.is_empty() {
+            return None;
+        }
+        Some(match self.folder.trim_end_matches('/').rsplit_once('/') {
+            Some((parent, _)) => format!("{parent}/"),
+            None => String::new(),
+        })
     }
 
     pub fn active_objects(&self) -> &[ObjectInfo] {
@@ -248,26 +492,29 @@ impl App {
         self.objects = objects;
         self.filtered_objects = Vec::new();
         self.selected_object = 0;
+        // Row indices change with the listing; the marks themselves are kept by key
+        self.mark_anchor = None;
     }
 
     pub fn append_objects(&mut self, mut new_objects: Vec<ObjectInfo>) {
         self.objects.append(&mut new_objects);
-        // Reapply mask if active
+        self.refresh_filtered();
+    }
+
+    /// Reapply the active mask to the loaded objects without reporting a status
+    pub fn refresh_filtered(&mut self) {
         if let Some(mask) = &self.active_mask {
             self.filtered_objects = self
                 .objects
                 .iter()
-                .filter(|&obj| {
-                    let key_matches = mask.matches(&obj.key);
-                    let storage_matches = mask
-                        .storage_class_filter
-                        .as_ref()
-                        .map(|filter| &obj.storage_class == filter)
-                        .unwrap_or(true);
-                    key_matches && storage_matches
-                })
+                .filter(|&obj| mask.matches_object(obj))
                 .cloned()
                 .collect();
+            // Keep the cursor where it was unless the row it was on dropped out;
+            // folder rows come first in folder view
+            self.selected_object = self
+                .selected_object
+                .min(self.object_rows().saturating_sub(1));
         }
     }
 
@@ -275,19 +522,152 @@ impl App {
         self.objects.clear();
         self.filtered_objects.clear();
         self.total_object_count = None;
+        self.listing_prefix = None;
         self.continuation_token = None;
         self.is_loading_objects = false;
         self.selected_object = 0;
+        self.folders.clear();
+    }
+
+    pub fn is_marked(&self, obj: &ObjectInfo) -> bool {
+        self.marked_keys.contains(&obj.key)
+    }
+
+    /// Mark or unmark the row under the cursor and make it the range anchor
+    pub fn toggle_mark(&mut self) {
+        let Some(key) = self.selected_object().map(|obj| obj.key.clone()) else {
+            return;
+        };
+        if !self.marked_keys.remove(&key) {
+            self.marked_keys.insert(key);
+        }
+        self.mark_anchor = Some(self.selected_object);
+    }
+
+    /// Mark every row between the anchor and the cursor
+    pub fn mark_range(&mut self) {
+        let anchor = *self.mark_anchor.get_or_insert(self.selected_object);
+        let (start, end) = if anchor <= self.selected_object {
+            (anchor, self.selected_object)
+        } else {
+            (self.selected_object, anchor)
+        };
+        // Folder rows can't be marked
+        let offset = self.folder_rows().len();
+        let Some(end) = end.checked_sub(offset) else {
+            return;
+        };
+        let start = start.saturating_sub(offset);
+        let keys: Vec<String> = self
+            .active_objects()
+            .iter()
+            .skip(start)
+            .take(end + 1 - start)
+            .map(|obj| obj.key.clone())
+            .collect();
+        self.marked_keys.extend(keys);
+    }
+
+    pub fn mark_all(&mut self) {
+        let keys: Vec<String> = self
+            .active_objects()
+            .iter()
+            .map(|obj| obj.key.clone())
+            .collect();
+        self.marked_keys.extend(keys);
+    }
+
+    /// Flip the mark on every row in the current view
+    pub fn invert_marks(&mut self) {
+        let keys: Vec<String> = self
+            .active_objects()
+            .iter()
+            .map(|obj| obj.key.clone())
+            .collect();
+        for key in keys {
+            if !self.marked_keys.remove(&key) {
+                self.marked_keys.insert(key);
+            }
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked_keys.clear();
+        self.mark_anchor = None;
+    }
+
+    /// After a reload, drop marks on keys the listing no longer has. Keys past the
+    /// last loaded page stay marked until their page arrives.
+    pub fn retain_listed_marks(&mut self) {
+        let last_loaded = self
+            .objects
+            .last()
+            .filter(|_| self.has_more_objects())
+            .map(|obj| obj.key.as_str());
+        let listed: HashSet<&str> = self.objects.iter().map(|obj| obj.key.as_str()).collect();
+        self.marked_keys.retain(|key| {
+            listed.contains(key.as_str()) || last_loaded.is_some_and(|last| key.as_str() > last)
+        });
+    }
+
+    /// Forget the pending action and everything resolved for it
+    pub fn clear_pending_action(&mut self) {
+        self.pending_action = None;
+        self.target_scan = None;
+        self.pending_targets = None;
+    }
+
+    /// Marked objects in view, in listing order
+    pub fn marked_objects(&self) -> Vec<ObjectInfo> {
+        self.active_objects()
+            .iter()
+            .filter(|obj| self.is_marked(obj))
+            .cloned()
+            .collect()
+    }
+
+    /// Objects an action applies to before any bucket-wide scan: the marked rows,
+    /// else the loaded mask matches, else the row under the cursor
+    pub fn action_objects(&self) -> Cow<'_, [ObjectInfo]> {
+        if !self.marked_keys.is_empty() {
+            Cow::Owned(self.marked_objects())
+        } else if self.active_mask.is_some() {
+            Cow::Borrowed(&self.filtered_objects)
+        } else if let Some(obj) = self.selected_object() {
+            Cow::Borrowed(std::slice::from_ref(obj))
+        } else {
+            Cow::Borrowed(&[])
+        }
     }
 
     pub fn has_more_objects(&self) -> bool {
         self.continuation_token.is_some()
     }
 
+    /// Server-side prefix the listing should use for the active mask
+    pub fn desired_listing_prefix(&self) -> Option<String> {
+        self.active_mask
+            .as_ref()
+            .and_then(|mask| mask.server_prefix())
+            .map(|prefix| prefix.to_string())
+    }
+
+    /// True when the loaded pages came from a listing that no longer fits the active mask.
+    /// Folder view always lists the current folder, so it is never stale.
+    pub fn listing_is_stale(&self) -> bool {
+        if self.folder_view {
+            return false;
+        }
+        let loaded = !self.objects.is_empty() || self.listing_prefix.is_some();
+        loaded && self.listing_prefix != self.desired_listing_prefix()
+    }
+
     pub fn should_load_more(&self) -> bool {
         // Load more if we're near the end (within last 50 items)
         let threshold = 50;
-        let current_pos = self.selected_object;
+        let current_pos = self
+            .selected_object
+            .saturating_sub(self.folder_rows().len());
         let loaded_count = self.objects.len();
 
         if loaded_count == 0 {
@@ -306,25 +686,48 @@ impl App {
         current_pos + threshold >= loaded_count && self.has_more_objects()
     }
 
+    /// Evaluate `mask` against the loaded objects for the mask editor preview
+    pub fn preview_mask(&mut self, mask: Result<ObjectMask, String>) {
+        let mask = match mask {
+            Ok(mask) => mask,
+            Err(err) => {
+                self.mask_preview = MaskPreview {
+                    error: Some(err),
+                    ..MaskPreview::default()
+                };
+                return;
+            }
+        };
+        let mut preview = MaskPreview::default();
+        for obj in &self.objects {
+            if !mask.matches_without_tags(obj) {
+                continue;
+            }
+            // Tags are only fetched once the mask is applied
+            if mask.needs_tags() && obj.tags.is_none() {
+                preview.untagged += 1;
+                continue;
+            }
+            if mask.matches_object(obj) {
+                preview.matches += 1;
+                if preview.keys.len() < MASK_PREVIEW_KEYS {
+                    preview.keys.push(obj.key.clone());
+                }
+            }
+        }
+        self.mask_preview = preview;
+    }
+
     pub fn apply_mask(&mut self, mask: Option<ObjectMask>) {
         self.active_mask = mask.clone();
+        // Marks belong to the view they were made in; an action must never reach
+        // rows the new mask hides
+        self.clear_marks();
         if let Some(mask) = mask {
             self.filtered_objects = self
                 .objects
                 .iter()
-                .filter(|&obj| {
-                    // Filter by key pattern
-                    let key_matches = mask.matches(&obj.key);
-
-                    // Filter by storage class if specified
-                    let storage_matches = mask
-                        .storage_class_filter
-                        .as_ref()
-                        .map(|filter| &obj.storage_class == filter)
-                        .unwrap_or(true); // If no filter, all storage classes match
-
-                    key_matches && storage_matches
-                })
+                .filter(|&obj| mask.matches_object(obj))
                 .cloned()
                 .collect();
             self.selected_object = 0;
@@ -371,16 +774,20 @@ impl App {
             MaskKind::Prefix => MaskKind::Suffix,
             MaskKind::Suffix => MaskKind::Contains,
             MaskKind::Contains => MaskKind::Regex,
-            MaskKind::Regex => MaskKind::Prefix,
+            MaskKind::Regex => MaskKind::Glob,
+            MaskKind::Glob => MaskKind::Expression,
+            MaskKind::Expression => MaskKind::Prefix,
         };
     }
 
     pub fn cycle_mask_kind_backwards(&mut self) {
         self.mask_draft.kind = match self.mask_draft.kind {
-            MaskKind::Prefix => MaskKind::Regex,
+            MaskKind::Prefix => MaskKind::Expression,
             MaskKind::Suffix => MaskKind::Prefix,
             MaskKind::Contains => MaskKind::Suffix,
             MaskKind::Regex => MaskKind::Contains,
+            MaskKind::Glob => MaskKind::Regex,
+            MaskKind::Expression => MaskKind::Glob,
         };
     }
 
@@ -392,30 +799,26 @@ impl App {
         self.mode = mode;
     }
 
+    /// Focus a mask editor field, putting the cursor at the end of text fields
     pub fn focus_mask_field(&mut self, field: MaskEditorField) {
         self.mask_field = field;
+        if let Some(text) = self.mask_draft.text_field(field) {
+            self.mask_draft.cursor_pos = text.len();
+        }
     }
 
     pub fn next_mask_field(&mut self) {
-        self.mask_field = self.mask_field.next();
+        self.focus_mask_field(self.mask_field.next());
     }
 
     pub fn previous_mask_field(&mut self) {
-        self.mask_field = self.mask_field.previous();
+        self.focus_mask_field(self.mask_field.previous());
     }
 
     /// Check if any of the targeted objects need restoration
     /// (i.e., they are in Glacier storage class and not already restored)
     pub fn any_targets_need_restoration(&self) -> bool {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return false;
-        };
-
-        objects.iter().any(|obj| {
+        self.action_objects().iter().any(|obj| {
             matches!(
                 obj.storage_class,
                 StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
@@ -429,15 +832,7 @@ impl App {
 
     /// Get count of objects that need restore (not already restored/restoring)
     pub fn count_objects_needing_restore(&self) -> usize {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return 0;
-        };
-
-        objects
+        self.action_objects()
             .iter()
             .filter(|obj| {
                 matches!(
@@ -455,15 +850,7 @@ impl App {
 
     /// Get count of objects already being restored
     pub fn count_objects_restoring(&self) -> usize {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return 0;
-        };
-
-        objects
+        self.action_objects()
             .iter()
             .filter(|obj| {
                 matches!(
@@ -1,13 +1,39 @@
 use anyhow::{Context, Result};
 use aws_sdk_s3::Client;
-use aws_sdk_s3::types::{MetadataDirective, RestoreRequest};
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::types::{
+    ChecksumAlgorithm, ChecksumMode, CompletedMultipartUpload, CompletedPart, GlacierJobParameters,
+    MetadataDirective, Object, RestoreRequest, StorageClass, TaggingDirective,
+};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use crate::models::{BucketInfo, ObjectInfo, RestoreState, StorageClassTier};
+use crate::models::{
+    BucketInfo, ObjectInfo, ObjectTags, RestoreState, RestoreStatus, RetrievalTier,
+    StorageClassTier,
+};
 
+/// Largest object a single CopyObject request accepts
+pub const MAX_COPY_OBJECT_SIZE: i64 = 5 * 1024 * 1024 * 1024;
+/// Multipart uploads allow at most 10,000 parts
+const MAX_UPLOAD_PARTS: i64 = 10_000;
+/// Smallest part used for UploadPartCopy; S3 itself only requires 5 MiB per part
+const MIN_COPY_PART_SIZE: i64 = 256 * 1024 * 1024;
+/// UploadPartCopy requests kept in flight for a single large object
+const PART_COPY_CONCURRENCY: usize = 8;
+/// GetObjectTagging requests kept in flight when tag masks are evaluated
+const TAG_FETCH_CONCURRENCY: usize = 10;
+
+/// Tags already fetched, keyed by bucket, key and last modified time so an
+/// overwritten object is fetched again
+type TagCache = HashMap<(String, String, Option<String>), ObjectTags>;
+
+#[derive(Clone)]
 pub struct S3Service {
     client: Client,
     region: Option<String>,
+    tag_cache: Arc<Mutex<TagCache>>,
 }
 
 impl S3Service {
@@ -15,7 +41,11 @@ impl S3Service {
         let config = aws_config::from_env().load().await;
         let region = config.region().map(|r| r.as_ref().to_string());
         let client = Client::new(&config);
-        Ok(Self { client, region })
+        Ok(Self {
+            client,
+            region,
+            tag_cache: Arc::default(),
+        })
     }
 
     pub fn region(&self) -> Option<&str> {
@@ -81,28 +111,52 @@ impl S3Service {
         }
         let response = request.send().await?;
 
-        let mut objects = Vec::new();
-        for object in response.contents() {
-            if let Some(key) = object.key() {
-                // Note: ListObjectsV2 does not return restore status, it's always None
-                // We fetch it separately for Glacier objects after loading
-                objects.push(ObjectInfo {
-                    key: key.to_string(),
-                    size: object.size().unwrap_or_default(),
-                    last_modified: object.last_modified().map(|dt| dt.to_string()),
-                    storage_class: StorageClassTier::from(object.storage_class().cloned()),
-                    restore_state: None, // Will be populated by batch_refresh_restore_status
-                });
-            }
+        let objects = response.contents().iter().filter_map(object_info).collect();
+        let next_token = if response.is_truncated().unwrap_or(false) {
+            response.next_continuation_token().map(|t| t.to_string())
+        } else {
+            None
+        };
+
+        Ok((objects, next_token))
+    }
+
+    /// Load a page of one folder with `delimiter="/"`: the subfolder prefixes
+    /// (common prefixes) and the objects directly inside it
+    pub async fn list_folder_paginated(
+        &self,
+        bucket: &str,
+        folder: &str,
+        continuation_token: Option<String>,
+        max_keys: i32,
+    ) -> Result<(Vec<String>, Vec<ObjectInfo>, Option<String>)> {
+        let mut request = self
+            .client
+            .list_objects_v2()
+            .bucket(bucket)
+            .delimiter("/")
+            .max_keys(max_keys);
+        if let Some(token) = continuation_token {
+            request = request.continuation_token(token);
         }
+        if !folder.is_empty() {
+            request = request.prefix(folder);
+        }
+        let response = request.send().await?;
 
+        let folders = response
+            .common_prefixes()
+            .iter()
+            .filter_map(|prefix| prefix.prefix().map(|p| p.to_string()))
+            .collect();
+        let objects = response.contents().iter().filter_map(object_info).collect();
         let next_token = if response.is_truncated().unwrap_or(false) {
             response.next_continuation_token().map(|t| t.to_string())
         } else {
             None
         };
 
-        Ok((objects, next_token))
+        Ok((folders, objects, next_token))
     }
 
     pub async fn refresh_object(&self, bucket: &str, key: &str) -> Result<ObjectInfo> {
@@ -120,26 +174,27 @@ impl S3Service {
             last_modified: head.last_modified().map(|dt| dt.to_string()),
             storage_class: StorageClassTier::from(head.storage_class().cloned()),
             restore_state: parse_restore_state(head.restore()),
+            tags: None,
         })
     }
 
     /// Batch refresh restore status for Glacier objects
-    /// Returns a map of key -> restore_state
+    /// Returns a map of key -> restore status, or None where HeadObject failed
     pub async fn batch_refresh_restore_status(
         &self,
         bucket: &str,
         keys: &[String],
-    ) -> Vec<(String, Option<RestoreState>)> {
+    ) -> Vec<(String, Option<RestoreStatus>)> {
         let mut results = Vec::new();
 
         // Make concurrent HeadObject calls (but limit concurrency)
         use futures::stream::{self, StreamExt};
 
         let chunk_size = 10; // Process 10 at a time
-        let mut stream = stream::iter(keys)
+        // Owned keys keep the future Send so it can run inside a spawned poll task
+        let mut stream = stream::iter(keys.to_vec())
             .map(|key| {
                 let bucket = bucket.to_string();
-                let key = key.to_string();
                 async move {
                     match self
                         .client
@@ -150,8 +205,11 @@ impl S3Service {
                         .await
                     {
                         Ok(head) => {
-                            let restore_state = parse_restore_state(head.restore());
-                            (key, restore_state)
+                            let status = RestoreStatus {
+                                state: parse_restore_state(head.restore()),
+                                expiry: parse_restore_expiry(head.restore()),
+                            };
+                            (key, Some(status))
                         }
                         Err(_) => {
                             // If HeadObject fails, keep the status unknown
@@ -169,6 +227,61 @@ impl S3Service {
         results
     }
 
+    /// Fetch tags for the given objects, reusing tags fetched earlier.
+    /// Returns key -> tags, or None where GetObjectTagging failed
+    pub async fn batch_fetch_tags(
+        &self,
+        bucket: &str,
+        objects: &[ObjectInfo],
+    ) -> Vec<(String, Option<ObjectTags>)> {
+        use futures::stream::{self, StreamExt};
+
+        let requests: Vec<(String, Option<String>)> = objects
+            .iter()
+            .map(|obj| (obj.key.clone(), obj.last_modified.clone()))
+            .collect();
+        stream::iter(requests)
+            .map(|(key, last_modified)| {
+                let cache_key = (bucket.to_string(), key, last_modified);
+                async move {
+                    let cached = self.tag_cache.lock().unwrap().get(&cache_key).cloned();
+                    if let Some(tags) = cached {
+                        return (cache_key.1, Some(tags));
+                    }
+                    match self
+                        .client
+                        .get_object_tagging()
+                        .bucket(&cache_key.0)
+                        .key(&cache_key.1)
+                        .send()
+                        .await
+                    {
+                        Ok(output) => {
+                            let tags: ObjectTags = output
+                                .tag_set()
+                                .iter()
+                                .map(|tag| (tag.key().to_string(), tag.value().to_string()))
+                                .collect();
+                            self.tag_cache
+                                .lock()
+                                .unwrap()
+                                .insert(cache_key.clone(), tags.clone());
+                            (cache_key.1, Some(tags))
+                        }
+                        Err(_) => (cache_key.1, None),
+                    }
+                }
+            })
+            .buffer_unordered(TAG_FETCH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Rewrite an object in place with a new storage class.
+    ///
+    /// The source is read first so its encryption, tags and checksum algorithm are
+    /// set explicitly on the copy instead of falling back to bucket defaults. Objects
+    /// over 5 GB can't go through CopyObject, so they are copied part by part.
     pub async fn transition_storage_class(
         &self,
         bucket: &str,
@@ -178,22 +291,194 @@ impl S3Service {
         let storage_class = target
             .to_sdk()
             .context("target storage class is not supported via API")?;
+        let head = self
+            .client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .checksum_mode(ChecksumMode::Enabled)
+            .send()
+            .await?;
         let source = format!("{}/{}", bucket, key);
         let encoded_source = urlencoding::encode(&source).into_owned();
+
+        if head.content_length().unwrap_or_default() > MAX_COPY_OBJECT_SIZE {
+            return self
+                .multipart_transition(bucket, key, &encoded_source, storage_class, &head)
+                .await;
+        }
         self.client
             .copy_object()
             .bucket(bucket)
             .key(key)
             .storage_class(storage_class)
             .copy_source(encoded_source)
+            .set_copy_source_if_match(head.e_tag().map(|etag| etag.to_string()))
             .metadata_directive(MetadataDirective::Copy)
+            .tagging_directive(TaggingDirective::Copy)
+            .set_server_side_encryption(head.server_side_encryption().cloned())
+            .set_ssekms_key_id(head.ssekms_key_id().map(|v| v.to_string()))
+            .set_bucket_key_enabled(head.bucket_key_enabled())
+            .set_checksum_algorithm(source_checksum_algorithm(&head))
             .send()
             .await?;
         Ok(())
     }
 
-    pub async fn request_restore(&self, bucket: &str, key: &str, days: i32) -> Result<()> {
-        let restore_request = RestoreRequest::builder().days(days).build();
+    /// CreateMultipartUpload + UploadPartCopy + CompleteMultipartUpload.
+    /// Unlike CopyObject this doesn't carry metadata over, so it is replayed from the
+    /// source's HEAD along with the object's tags.
+    async fn multipart_transition(
+        &self,
+        bucket: &str,
+        key: &str,
+        encoded_source: &str,
+        storage_class: StorageClass,
+        head: &HeadObjectOutput,
+    ) -> Result<()> {
+        use futures::stream::{self, StreamExt, TryStreamExt};
+
+        let size = head.content_length().unwrap_or_default();
+        let etag = head.e_tag().map(|etag| etag.to_string());
+
+        let tags = self
+            .client
+            .get_object_tagging()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await?;
+        let tagging = tags
+            .tag_set()
+            .iter()
+            .map(|tag| {
+                format!(
+                    "{}={}",
+                    urlencoding::encode(tag.key()),
+                    urlencoding::encode(tag.value())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .storage_class(storage_class)
+            .set_metadata(head.metadata().cloned())
+            .set_content_type(head.content_type().map(|v| v.to_string()))
+            .set_content_encoding(head.content_encoding().map(|v| v.to_string()))
+            .set_content_disposition(head.content_disposition().map(|v| v.to_string()))
+            .set_content_language(head.content_language().map(|v| v.to_string()))
+            .set_cache_control(head.cache_control().map(|v| v.to_string()))
+            .set_tagging((!tagging.is_empty()).then_some(tagging))
+            .set_server_side_encryption(head.server_side_encryption().cloned())
+            .set_ssekms_key_id(head.ssekms_key_id().map(|v| v.to_string()))
+            .set_bucket_key_enabled(head.bucket_key_enabled())
+            .set_checksum_algorithm(source_checksum_algorithm(head))
+            .set_checksum_type(head.checksum_type().cloned())
+            .send()
+            .await?;
+        let upload_id = upload
+            .upload_id()
+            .context("CreateMultipartUpload returned no upload id")?
+            .to_string();
+
+        let part_size = MIN_COPY_PART_SIZE.max((size + MAX_UPLOAD_PARTS - 1) / MAX_UPLOAD_PARTS);
+        let ranges: Vec<(i32, i64, i64)> = (0..size)
+            .step_by(part_size as usize)
+            .enumerate()
+            .map(|(idx, start)| (idx as i32 + 1, start, (start + part_size).min(size) - 1))
+            .collect();
+
+        let copied: Result<Vec<CompletedPart>> = stream::iter(ranges)
+            .map(|(part_number, start, end)| {
+                let (upload_id, etag) = (&upload_id, &etag);
+                async move {
+                    let output = self
+                        .client
+                        .upload_part_copy()
+                        .bucket(bucket)
+                        .key(key)
+                        .upload_id(upload_id)
+                        .part_number(part_number)
+                        .copy_source(encoded_source)
+                        .copy_source_range(format!("bytes={start}-{end}"))
+                        // Fail rather than stitch together parts of two different versions
+                        .set_copy_source_if_match(etag.clone())
+                        .send()
+                        .await?;
+                    let result = output
+                        .copy_part_result()
+                        .context("UploadPartCopy returned no result")?;
+                    let part_etag = result.e_tag().context("UploadPartCopy returned no ETag")?;
+                    // With a checksum algorithm set, completing the upload needs every part's checksum
+                    Ok(CompletedPart::builder()
+                        .part_number(part_number)
+                        .e_tag(part_etag)
+                        .set_checksum_crc32(result.checksum_crc32().map(|v| v.to_string()))
+                        .set_checksum_crc32_c(result.checksum_crc32_c().map(|v| v.to_string()))
+                        .set_checksum_crc64_nvme(
+                            result.checksum_crc64_nvme().map(|v| v.to_string()),
+                        )
+                        .set_checksum_sha1(result.checksum_sha1().map(|v| v.to_string()))
+                        .set_checksum_sha256(result.checksum_sha256().map(|v| v.to_string()))
+                        .build())
+                }
+            })
+            .buffered(PART_COPY_CONCURRENCY)
+            .try_collect()
+            .await;
+
+        let result = match copied {
+            Ok(parts) => self
+                .client
+                .complete_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(anyhow::Error::from),
+            Err(err) => Err(err),
+        };
+
+        if result.is_err() {
+            // Don't leave billable orphaned parts behind
+            let _ = self
+                .client
+                .abort_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+        }
+        result
+    }
+
+    pub async fn request_restore(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: i32,
+        tier: RetrievalTier,
+    ) -> Result<()> {
+        let job_parameters = GlacierJobParameters::builder()
+            .tier(tier.to_sdk())
+            .build()?;
+        let restore_request = RestoreRequest::builder()
+            .days(days)
+            .glacier_job_parameters(job_parameters)
+            .build();
 
         self.client
             .restore_object()
@@ -207,25 +492,62 @@ impl S3Service {
     }
 }
 
+/// HeadObject only reports checksum values, so infer which algorithm produced them
+fn source_checksum_algorithm(head: &HeadObjectOutput) -> Option<ChecksumAlgorithm> {
+    if head.checksum_crc64_nvme().is_some() {
+        Some(ChecksumAlgorithm::Crc64Nvme)
+    } else if head.checksum_crc32_c().is_some() {
+        Some(ChecksumAlgorithm::Crc32C)
+    } else if head.checksum_crc32().is_some() {
+        Some(ChecksumAlgorithm::Crc32)
+    } else if head.checksum_sha256().is_some() {
+        Some(ChecksumAlgorithm::Sha256)
+    } else if head.checksum_sha1().is_some() {
+        Some(ChecksumAlgorithm::Sha1)
+    } else {
+        None
+    }
+}
+
+/// Parse the `x-amz-restore` header. A finished restore reports
+/// `ongoing-request="false"` together with the expiry date of the restored copy.
 fn parse_restore_state(raw: Option<&str>) -> Option<RestoreState> {
     raw.map(|value| {
         let value = value.to_ascii_lowercase();
         if value.contains("ongoing-request=\"true\"") {
             RestoreState::InProgress { expiry: None }
-        } else if let Some(expiry) = value
-            .split("expiry-date=\"")
-            .nth(1)
-            .and_then(|part| part.split('"').next())
-        {
-            DateTime::parse_from_rfc2822(expiry)
-                .map(|dt| RestoreState::InProgress {
-                    expiry: Some(dt.with_timezone(&Utc).to_rfc3339()),
-                })
-                .unwrap_or(RestoreState::Available)
         } else if value.contains("ongoing-request=\"false\"") {
-            RestoreState::Available
+            let expired = parse_restore_expiry(Some(&value))
+                .and_then(|expiry| DateTime::parse_from_rfc3339(&expiry).ok())
+                .is_some_and(|expiry| expiry < Utc::now());
+            if expired {
+                RestoreState::Expired
+            } else {
+                RestoreState::Available
+            }
         } else {
             RestoreState::Expired
         }
     })
 }
+
+/// Expiry date of a restored copy from the `x-amz-restore` header, as RFC 3339
+fn parse_restore_expiry(raw: Option<&str>) -> Option<String> {
+    let expiry = raw?.split("expiry-date=\"").nth(1)?.split('"').next()?;
+    DateTime::parse_from_rfc2822(expiry)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+}
+
+fn object_info(object: &Object) -> Option<ObjectInfo> {
+    // Note: ListObjectsV2 does not return restore status, it's always None
+    // We fetch it separately for Glacier objects after loading
+    Some(ObjectInfo {
+        key: object.key()?.to_string(),
+        size: object.size().unwrap_or_default(),
+        last_modified: object.last_modified().map(|dt| dt.to_string()),
+        storage_class: StorageClassTier::from(object.storage_class().cloned()),
+        restore_state: None, // Will be populated by batch_refresh_restore_status
+        tags: None,
+    })
+}
@@ -0,0 +1,669 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+
+use crate::app::PendingAction;
+use crate::aws::S3Service;
+use crate::dry_run;
+use crate::jobs::{Job, JobStatus};
+use crate::journal::Journal;
+use crate::mask::{DateFilter, MaskKind, ObjectMask, SizeFilter, TagFilter};
+use crate::models::{
+    ObjectInfo, RestoreState, RetrievalTier, StorageClassTier, TrackedRestoreRequest, format_size,
+};
+use crate::plan::{self, Plan};
+use crate::scan::{FullScan, ScanStatus};
+use crate::settings::Settings;
+use crate::tracker::{RestorePoll, RestoreTracker};
+
+/// How often headless commands drain background scan and job events
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Interactive S3 storage class and Glacier restore manager.
+///
+/// Runs the TUI when no subcommand is given.
+#[derive(Parser)]
+#[command(name = "bucket-brigade", version)]
+pub struct Cli {
+    /// Print JSON instead of human-readable output
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Write the requests a transition or restore would send to a plan file instead of
+    /// calling S3. Also starts the TUI with dry-run mode on.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// List accessible buckets
+    ListBuckets,
+    /// List the objects in a bucket, optionally filtered by a mask
+    Ls {
+        /// Bucket name
+        bucket: String,
+        #[command(flatten)]
+        mask: MaskArgs,
+    },
+    /// Transition every object matching the mask to another storage class
+    Transition {
+        /// Bucket name
+        bucket: String,
+        #[command(flatten)]
+        mask: MaskArgs,
+        /// Target storage class, e.g. GLACIER_IR or DEEP_ARCHIVE
+        #[arg(long = "to", value_parser = parse_storage_class)]
+        target: StorageClassTier,
+    },
+    /// Request Glacier restores for every archived object matching the mask
+    Restore {
+        /// Bucket name
+        bucket: String,
+        #[command(flatten)]
+        mask: MaskArgs,
+        /// How long the restored copy stays available
+        #[arg(long, default_value_t = 7)]
+        days: i32,
+        /// Retrieval tier: expedited, standard or bulk
+        #[arg(long, default_value = "standard", value_parser = parse_retrieval_tier)]
+        tier: RetrievalTier,
+    },
+    /// Re-check a plan saved from the TUI and run it for the keys that still qualify
+    ApplyPlan {
+        /// Plan file written by the confirm step (`w`)
+        path: PathBuf,
+    },
+    /// Inspect tracked restore requests
+    Restores {
+        #[command(subcommand)]
+        command: RestoresCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RestoresCommand {
+    /// Refresh in-progress restores and print every tracked request
+    Status {
+        /// Print the saved state without checking S3
+        #[arg(long)]
+        no_refresh: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct MaskArgs {
+    /// Pattern matched against object keys
+    #[arg(long)]
+    mask: Option<String>,
+    /// How the pattern is matched: prefix, suffix, contains, regex, glob or expression
+    #[arg(long, default_value = "prefix", value_parser = parse_mask_kind)]
+    kind: MaskKind,
+    /// Match the pattern case-sensitively (the default for prefix, glob and expression)
+    #[arg(long, conflicts_with = "ignore_case")]
+    case_sensitive: bool,
+    /// Match the pattern case-insensitively; prefix, glob and expression masks then
+    /// can't be listed server-side and the whole bucket is scanned
+    #[arg(long)]
+    ignore_case: bool,
+    /// Only include objects currently in this storage class
+    #[arg(long, value_parser = parse_storage_class)]
+    storage_class: Option<StorageClassTier>,
+    /// Only include objects larger or smaller than a size, e.g. "> 1GiB" or "< 128KiB"
+    #[arg(long, value_parser = parse_size_filter)]
+    size: Option<SizeFilter>,
+    /// Only include objects by last modified: "> 90d" (older), "< 7d" (newer) or
+    /// "2023-01-01..2023-06-30"
+    #[arg(long, value_parser = parse_date_filter)]
+    modified: Option<DateFilter>,
+    /// Only include objects by tag: "retention=short", "project exists" or "project missing"
+    #[arg(long, value_parser = parse_tag_filter)]
+    tag: Option<TagFilter>,
+}
+
+impl MaskArgs {
+    fn to_mask(&self) -> Result<Option<ObjectMask>> {
+        if self.mask.is_none()
+            && self.storage_class.is_none()
+            && self.size.is_none()
+            && self.modified.is_none()
+            && self.tag.is_none()
+        {
+            return Ok(None);
+        }
+        let (pattern, kind) = match &self.mask {
+            Some(pattern) => (pattern.clone(), self.kind.clone()),
+            // Without a pattern only the other filters narrow the listing
+            None => (String::new(), MaskKind::Prefix),
+        };
+        // S3 keys are case-sensitive, so kinds with a literal prefix default to matching
+        // them exactly and keep the prefix pushed down to the listing
+        let pushes_down = matches!(
+            kind,
+            MaskKind::Prefix | MaskKind::Glob | MaskKind::Expression
+        );
+        let case_sensitive = !self.ignore_case && (self.case_sensitive || pushes_down);
+        if self.ignore_case && pushes_down && !pattern.is_empty() {
+            eprintln!(
+                "Note: --ignore-case can't be listed by prefix, so the whole bucket is scanned"
+            );
+        }
+        let mut mask = ObjectMask::new("cli".to_string(), pattern, kind, case_sensitive)
+            .context("invalid --mask")?;
+        mask.storage_class_filter = self.storage_class.clone();
+        mask.size_filter = self.size;
+        mask.modified_filter = self.modified;
+        mask.tag_filter = self.tag.clone();
+        Ok(Some(mask))
+    }
+
+    /// Bulk actions refuse to run against a whole bucket by accident
+    fn require_mask(&self, command: &str) -> Result<ObjectMask> {
+        self.to_mask()?.with_context(|| {
+            format!(
+                "{command} needs --mask, --storage-class, --size, --modified and/or --tag to select objects"
+            )
+        })
+    }
+}
+
+fn parse_mask_kind(value: &str) -> Result<MaskKind, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "prefix" => Ok(MaskKind::Prefix),
+        "suffix" => Ok(MaskKind::Suffix),
+        "contains" => Ok(MaskKind::Contains),
+        "regex" => Ok(MaskKind::Regex),
+        "glob" => Ok(MaskKind::Glob),
+        "expr" | "expression" => Ok(MaskKind::Expression),
+        _ => Err("expected prefix, suffix, contains, regex, glob or expression".to_string()),
+    }
+}
+
+fn parse_storage_class(value: &str) -> Result<StorageClassTier, String> {
+    StorageClassTier::from_label(value).ok_or_else(|| {
+        let labels: Vec<&str> = StorageClassTier::all_for_filter()
+            .into_iter()
+            .filter_map(|(label, tier)| tier.map(|_| label))
+            .collect();
+        format!("expected one of {}", labels.join(", "))
+    })
+}
+
+fn parse_size_filter(value: &str) -> Result<SizeFilter, String> {
+    value.parse()
+}
+
+fn parse_date_filter(value: &str) -> Result<DateFilter, String> {
+    value.parse()
+}
+
+fn parse_tag_filter(value: &str) -> Result<TagFilter, String> {
+    value.parse()
+}
+
+fn parse_retrieval_tier(value: &str) -> Result<RetrievalTier, String> {
+    RetrievalTier::ALL
+        .into_iter()
+        .find(|tier| tier.label().eq_ignore_ascii_case(value))
+        .ok_or_else(|| "expected expedited, standard or bulk".to_string())
+}
+
+/// Run a headless subcommand
+pub async fn run(command: Command, json: bool, dry_run: bool, settings: &Settings) -> Result<()> {
+    let s3 = S3Service::new().await?;
+    match command {
+        Command::ListBuckets => list_buckets(&s3, json).await,
+        Command::Ls { bucket, mask } => list_objects(&s3, bucket, mask.to_mask()?, json).await,
+        Command::Transition {
+            bucket,
+            mask,
+            target,
+        } => {
+            let mask = mask.require_mask("transition")?;
+            let objects = scan(&s3, &bucket, mask, json).await?;
+            let action = PendingAction::Transition {
+                target_class: target,
+            };
+            if dry_run {
+                return write_dry_run(&bucket, &action, &objects, json);
+            }
+            run_job(&s3, bucket, action, objects, settings, None, json).await
+        }
+        Command::Restore {
+            bucket,
+            mask,
+            days,
+            tier,
+        } => {
+            let mask = mask.require_mask("restore")?;
+            let mut tracker = RestoreTracker::new()?;
+            let objects = scan(&s3, &bucket, mask, json).await?;
+            let restoring: HashSet<String> = tracker
+                .get_active_requests()
+                .into_iter()
+                .filter(|req| req.bucket == bucket)
+                .map(|req| req.key)
+                .collect();
+            // Only archived objects need a restore; skip ones already being restored
+            let objects: Vec<ObjectInfo> = objects
+                .into_iter()
+                .filter(|obj| {
+                    matches!(
+                        obj.storage_class,
+                        StorageClassTier::GlacierFlexibleRetrieval
+                            | StorageClassTier::GlacierDeepArchive
+                    )
+                })
+                .filter(|obj| !restoring.contains(&obj.key))
+                .collect();
+            // Listings carry no restore status, so HEAD the rest before requesting
+            let objects = skip_restored(&s3, &bucket, objects, settings.concurrency(), json).await;
+            let action = PendingAction::Restore { days, tier };
+            if dry_run {
+                return write_dry_run(&bucket, &action, &objects, json);
+            }
+            run_job(
+                &s3,
+                bucket,
+                action,
+                objects,
+                settings,
+                Some(&mut tracker),
+                json,
+            )
+            .await
+        }
+        Command::ApplyPlan { path } => apply_plan(&s3, &path, settings, json, dry_run).await,
+        Command::Restores {
+            command: RestoresCommand::Status { no_refresh },
+        } => restores_status(&s3, no_refresh, json).await,
+    }
+}
+
+async fn apply_plan(
+    s3: &S3Service,
+    path: &std::path::Path,
+    settings: &Settings,
+    json: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let plan = Plan::load(path)?;
+    if !json {
+        eprintln!(
+            "Checking {} planned objects in {}…",
+            plan.objects.len(),
+            plan.bucket
+        );
+    }
+    let (objects, skipped) = plan::revalidate(s3, &plan, settings.concurrency()).await;
+    if !json {
+        for (key, reason) in &skipped {
+            eprintln!("skip {key}: {reason}");
+        }
+        eprintln!(
+            "{} objects still match the plan, {} skipped",
+            objects.len(),
+            skipped.len()
+        );
+    }
+
+    let Plan { bucket, action, .. } = plan;
+    if dry_run {
+        return write_dry_run(&bucket, &action, &objects, json);
+    }
+    let mut tracker = match action {
+        PendingAction::Restore { .. } => Some(RestoreTracker::new()?),
+        PendingAction::Transition { .. } => None,
+    };
+    run_job(
+        s3,
+        bucket,
+        action,
+        objects,
+        settings,
+        tracker.as_mut(),
+        json,
+    )
+    .await
+}
+
+async fn list_buckets(s3: &S3Service, json: bool) -> Result<()> {
+    let buckets = s3.list_buckets().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&buckets)?);
+        return Ok(());
+    }
+    for bucket in buckets {
+        println!(
+            "{:<25} {:<15} {}",
+            bucket.creation_date.as_deref().unwrap_or("-"),
+            bucket.region.as_deref().unwrap_or("-"),
+            bucket.name
+        );
+    }
+    Ok(())
+}
+
+async fn list_objects(
+    s3: &S3Service,
+    bucket: String,
+    mask: Option<ObjectMask>,
+    json: bool,
+) -> Result<()> {
+    // An empty pattern matches every key, so an unmasked listing is just a catch-all mask
+    let mask = match mask {
+        Some(mask) => mask,
+        None => ObjectMask::new("cli".to_string(), String::new(), MaskKind::Prefix, true)?,
+    };
+    // Print each page as it arrives instead of waiting for the whole bucket
+    let mut scan = FullScan::start(s3, bucket.clone(), mask);
+    let mut printed = 0;
+    if json {
+        print!("[");
+    }
+    loop {
+        let finished = scan.poll();
+        for obj in &scan.targets[printed..] {
+            if json {
+                let separator = if printed == 0 { "" } else { "," };
+                print!("{separator}\n  {}", serde_json::to_string(obj)?);
+            } else {
+                println!(
+                    "{:<20} {:>12} {:<25} {}",
+                    obj.storage_class.label(),
+                    format_size(obj.size).trim_start(),
+                    obj.last_modified.as_deref().unwrap_or("-"),
+                    obj.key
+                );
+            }
+            printed += 1;
+        }
+        if finished {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    if json {
+        println!("{}]", if printed == 0 { "" } else { "\n" });
+    }
+    if let ScanStatus::Failed(err) = &scan.status {
+        bail!("listing {bucket} failed: {err}");
+    }
+    if !json {
+        eprintln!(
+            "{} of {} objects matched ({})",
+            scan.targets.len(),
+            scan.listed,
+            format_size(scan.total_bytes).trim_start()
+        );
+    }
+    Ok(())
+}
+
+/// Resolve every object in the bucket that matches the mask
+async fn scan(
+    s3: &S3Service,
+    bucket: &str,
+    mask: ObjectMask,
+    json: bool,
+) -> Result<Vec<ObjectInfo>> {
+    let mut scan = FullScan::start(s3, bucket.to_string(), mask);
+    while !scan.poll() {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    if let ScanStatus::Failed(err) = &scan.status {
+        bail!("listing {bucket} failed: {err}");
+    }
+    if !json {
+        eprintln!(
+            "{} of {} objects matched ({})",
+            scan.targets.len(),
+            scan.listed,
+            format_size(scan.total_bytes).trim_start()
+        );
+    }
+    Ok(std::mem::take(&mut scan.targets))
+}
+
+/// HEAD each archived object and drop those already restored or being restored.
+/// Objects whose HEAD fails are kept, so the restore itself reports the error.
+async fn skip_restored(
+    s3: &S3Service,
+    bucket: &str,
+    objects: Vec<ObjectInfo>,
+    concurrency: usize,
+    json: bool,
+) -> Vec<ObjectInfo> {
+    use futures::stream::{self, StreamExt};
+
+    let checked: Vec<(ObjectInfo, Option<RestoreState>)> = stream::iter(objects)
+        .map(|obj| async move {
+            let state = s3
+                .refresh_object(bucket, &obj.key)
+                .await
+                .ok()
+                .and_then(|current| current.restore_state);
+            (obj, state)
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let mut skipped = 0;
+    let mut pending = Vec::new();
+    for (obj, state) in checked {
+        let reason = match state {
+            Some(RestoreState::Available) => "already restored",
+            Some(RestoreState::InProgress { .. }) => "restore already in progress",
+            _ => {
+                pending.push(obj);
+                continue;
+            }
+        };
+        if !json {
+            eprintln!("skip {}: {reason}", obj.key);
+        }
+        skipped += 1;
+    }
+    if !json && skipped > 0 {
+        eprintln!("{skipped} objects already restored or restoring, skipped");
+    }
+    pending
+}
+
+fn write_dry_run(
+    bucket: &str,
+    action: &PendingAction,
+    objects: &[ObjectInfo],
+    json: bool,
+) -> Result<()> {
+    let report = dry_run::write_plan(bucket, action, objects)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Dry run: {} requests ({}) written to {}",
+            report.requests,
+            format_size(report.total_bytes).trim_start(),
+            report.path.display()
+        );
+        for (class, totals) in &report.by_source_class {
+            println!(
+                "  {:<20} {:>8} objects {:>12}",
+                class,
+                totals.objects,
+                format_size(totals.bytes).trim_start()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JobReport<'a> {
+    bucket: &'a str,
+    operation: &'a str,
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    retried: usize,
+    not_started: usize,
+    failures: Vec<FailedKey>,
+}
+
+#[derive(Serialize)]
+struct FailedKey {
+    key: String,
+    class: String,
+    message: String,
+}
+
+/// Run a bulk action as a job and wait for it; Ctrl-C cancels after the objects in flight
+async fn run_job(
+    s3: &S3Service,
+    bucket: String,
+    action: PendingAction,
+    objects: Vec<ObjectInfo>,
+    settings: &Settings,
+    mut tracker: Option<&mut RestoreTracker>,
+    json: bool,
+) -> Result<()> {
+    if objects.is_empty() {
+        if !json {
+            eprintln!("Nothing to do");
+        }
+        return Ok(());
+    }
+
+    // The job still runs without a journal; it just can't be resumed
+    let journal = match Journal::create(&bucket, &action, &objects) {
+        Ok(journal) => Some(journal),
+        Err(err) => {
+            eprintln!("Warning: could not create job journal, the job won't be resumable: {err:#}");
+            None
+        }
+    };
+    let mut job = Job::spawn(
+        1,
+        s3,
+        bucket.clone(),
+        action.clone(),
+        objects,
+        settings,
+        journal,
+    );
+    let mut failures = Vec::new();
+    let mut last_report = std::time::Instant::now();
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    while job.is_running() {
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            _ = &mut ctrl_c, if !job.is_cancelling() => {
+                eprintln!("Cancelling after the objects in flight finish…");
+                job.cancel();
+            }
+        }
+        let mut tracked_keys = Vec::new();
+        for outcome in job.poll() {
+            match (&action, outcome.failure) {
+                (_, Some(failure)) => {
+                    if !json {
+                        eprintln!("✗ {}: {}", outcome.key, failure.message);
+                    }
+                    failures.push(FailedKey {
+                        key: outcome.key,
+                        class: failure.class.to_string(),
+                        message: failure.message,
+                    });
+                }
+                (PendingAction::Restore { .. }, None) => tracked_keys.push(outcome.key),
+                (PendingAction::Transition { .. }, None) => {}
+            }
+        }
+        if let (PendingAction::Restore { days, tier }, Some(tracker)) =
+            (&action, tracker.as_deref_mut())
+        {
+            tracker.add_requests(&bucket, tracked_keys, *days, *tier);
+        }
+        if !json && job.is_running() && last_report.elapsed() >= Duration::from_secs(2) {
+            eprintln!("{}", job.summary());
+            last_report = std::time::Instant::now();
+        }
+    }
+
+    if json {
+        let report = JobReport {
+            bucket: &bucket,
+            operation: &job.progress.operation,
+            total: job.progress.total,
+            succeeded: job.succeeded,
+            failed: job.failed,
+            retried: job.retried,
+            not_started: job.skipped.len(),
+            failures,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", job.summary());
+    }
+
+    match &job.status {
+        JobStatus::Completed if job.failed == 0 => Ok(()),
+        JobStatus::Completed => bail!("{} objects failed", job.failed),
+        JobStatus::Cancelled => bail!(
+            "cancelled with {} objects not started; resume from the TUI's startup prompt",
+            job.skipped.len()
+        ),
+        JobStatus::Failed(err) => bail!("{err}"),
+        JobStatus::Running => unreachable!("loop exits once the job stops running"),
+    }
+}
+
+async fn restores_status(s3: &S3Service, no_refresh: bool, json: bool) -> Result<()> {
+    let mut tracker = RestoreTracker::new()?;
+    if !no_refresh {
+        let mut poll = RestorePoll::start(s3, &tracker.get_active_requests());
+        let results = loop {
+            if let Some(results) = poll.take_results().await {
+                break results;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+        tracker.apply_poll(results);
+    }
+
+    let requests = tracker.get_all_requests();
+    if json {
+        println!("{}", serde_json::to_string_pretty(requests)?);
+        return Ok(());
+    }
+    for req in requests {
+        println!(
+            "{:<12} {:<10} {:>4}d  {:<26} {}/{}",
+            restore_state_label(req),
+            req.tier.map(|tier| tier.label()).unwrap_or("-"),
+            req.days,
+            req.expires_at.as_deref().unwrap_or("-"),
+            req.bucket,
+            req.key
+        );
+    }
+    Ok(())
+}
+
+fn restore_state_label(req: &TrackedRestoreRequest) -> &'static str {
+    match req.current_status {
+        RestoreState::InProgress { .. } => "in-progress",
+        RestoreState::Available => "available",
+        RestoreState::Expired => "expired",
+    }
+}
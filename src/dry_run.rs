@@ -0,0 +1,118 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::app::PendingAction;
+use crate::aws::MAX_COPY_OBJECT_SIZE;
+use crate::models::{ObjectInfo, RetrievalTier, format_size};
+use crate::settings::{config_dir, create_unique_file};
+
+/// One request a bulk action would have sent, written as a JSON line
+#[derive(Serialize)]
+struct PlannedRequest<'a> {
+    operation: &'static str,
+    bucket: &'a str,
+    key: &'a str,
+    size: i64,
+    source_storage_class: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_storage_class: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tier: Option<RetrievalTier>,
+}
+
+/// Totals for the objects currently in one storage class
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ClassTotals {
+    pub objects: usize,
+    pub bytes: i64,
+}
+
+/// What a dry run would have done and where its plan was written
+#[derive(Debug, Serialize)]
+pub struct DryRunReport {
+    pub path: PathBuf,
+    pub requests: usize,
+    pub total_bytes: i64,
+    pub by_source_class: BTreeMap<String, ClassTotals>,
+}
+
+impl DryRunReport {
+    /// e.g. "GLACIER: 120 objects, 3355443.20 KB; STANDARD: 4 objects, 12.00 KB"
+    pub fn class_breakdown(&self) -> String {
+        self.by_source_class
+            .iter()
+            .map(|(class, totals)| {
+                format!(
+                    "{class}: {} objects, {}",
+                    totals.objects,
+                    format_size(totals.bytes).trim_start()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Write every CopyObject/RestoreObject the action would issue to a JSON lines
+/// file under `dry-runs/` in the config directory, without calling S3.
+pub fn write_plan(
+    bucket: &str,
+    action: &PendingAction,
+    objects: &[ObjectInfo],
+) -> Result<DryRunReport> {
+    let stem = format!("{}-{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ"), bucket);
+    let (path, file) = create_unique_file(&config_dir()?.join("dry-runs"), &stem, "jsonl")?;
+
+    let mut writer = BufWriter::new(file);
+    let mut by_source_class: BTreeMap<String, ClassTotals> = BTreeMap::new();
+    for obj in objects {
+        let request = match action {
+            PendingAction::Transition { target_class } => PlannedRequest {
+                // Objects over 5 GB go through UploadPartCopy instead
+                operation: if obj.size > MAX_COPY_OBJECT_SIZE {
+                    "MultipartCopy"
+                } else {
+                    "CopyObject"
+                },
+                bucket,
+                key: &obj.key,
+                size: obj.size,
+                source_storage_class: obj.storage_class.label(),
+                target_storage_class: Some(target_class.label()),
+                days: None,
+                tier: None,
+            },
+            PendingAction::Restore { days, tier } => PlannedRequest {
+                operation: "RestoreObject",
+                bucket,
+                key: &obj.key,
+                size: obj.size,
+                source_storage_class: obj.storage_class.label(),
+                target_storage_class: None,
+                days: Some(*days),
+                tier: Some(*tier),
+            },
+        };
+        serde_json::to_writer(&mut writer, &request)?;
+        writer.write_all(b"\n")?;
+
+        let totals = by_source_class
+            .entry(obj.storage_class.label().to_string())
+            .or_default();
+        totals.objects += 1;
+        totals.bytes += obj.size;
+    }
+    writer.flush()?;
+
+    Ok(DryRunReport {
+        path,
+        requests: objects.len(),
+        total_bytes: objects.iter().map(|obj| obj.size).sum(),
+        by_source_class,
+    })
+}
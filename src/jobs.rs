@@ -0,0 +1,434 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::operation::restore_object::RestoreObjectError;
+use futures::stream::{self, StreamExt};
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender, error::TryRecvError};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::app::{PendingAction, ProgressState};
+use crate::aws::S3Service;
+use crate::journal::Journal;
+use crate::models::ObjectInfo;
+use crate::retry::{FailureClass, RetryPolicy};
+use crate::settings::Settings;
+
+pub type JobId = usize;
+
+enum JobEvent {
+    ItemStarted(String),
+    ItemFinished(ItemOutcome),
+    ItemSkipped(ObjectInfo),
+    Finished,
+}
+
+/// Requested state for a job's workers, checked before each object is started
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobControl {
+    Run,
+    Pause,
+    Cancel,
+}
+
+/// Result of a single per-object request within a job
+pub struct ItemOutcome {
+    pub key: String,
+    pub attempts: u32,
+    pub failure: Option<ItemFailure>,
+}
+
+/// Permanent failure of an object after retries were exhausted
+pub struct ItemFailure {
+    pub class: FailureClass,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Cancelled,
+    Failed(String),
+}
+
+/// A confirmed bulk action running as a background tokio task
+pub struct Job {
+    pub id: JobId,
+    pub bucket: String,
+    pub action: PendingAction,
+    pub progress: ProgressState,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub retried: usize,
+    pub failures: BTreeMap<FailureClass, usize>,
+    /// Objects that were never started because the job was cancelled
+    pub skipped: Vec<ObjectInfo>,
+    pub status: JobStatus,
+    /// Persisted per-key outcomes; removed once the job completes
+    pub journal: Option<Journal>,
+    control: watch::Sender<JobControl>,
+    receiver: Option<UnboundedReceiver<JobEvent>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Job {
+    pub fn spawn(
+        id: JobId,
+        s3: &S3Service,
+        bucket: String,
+        action: PendingAction,
+        objects: Vec<ObjectInfo>,
+        settings: &Settings,
+        journal: Option<Journal>,
+    ) -> Self {
+        let operation = match &action {
+            PendingAction::Transition { target_class } => {
+                format!("Transitioning to {}", target_class.label())
+            }
+            PendingAction::Restore { tier, .. } => {
+                format!("Requesting {} Glacier restore", tier.label())
+            }
+        };
+        let progress = ProgressState::new(operation, objects.len());
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (control, control_rx) = watch::channel(JobControl::Run);
+        let handle = tokio::spawn(run_job(
+            s3.clone(),
+            bucket.clone(),
+            action.clone(),
+            objects,
+            settings.clone(),
+            control_rx,
+            tx,
+        ));
+        Self {
+            id,
+            bucket,
+            action,
+            progress,
+            succeeded: 0,
+            failed: 0,
+            retried: 0,
+            failures: BTreeMap::new(),
+            skipped: Vec::new(),
+            status: JobStatus::Running,
+            journal,
+            control,
+            receiver: Some(rx),
+            handle: Some(handle),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == JobStatus::Running
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_running() && *self.control.borrow() == JobControl::Pause
+    }
+
+    /// Stop starting new objects; requests already in flight are allowed to finish
+    pub fn pause(&self) {
+        self.set_control(JobControl::Pause);
+    }
+
+    pub fn resume(&self) {
+        self.set_control(JobControl::Run);
+    }
+
+    /// Finish the objects in flight and skip everything else
+    pub fn cancel(&self) {
+        self.set_control(JobControl::Cancel);
+    }
+
+    pub fn is_cancelling(&self) -> bool {
+        self.is_running() && *self.control.borrow() == JobControl::Cancel
+    }
+
+    fn set_control(&self, state: JobControl) {
+        if self.is_running() {
+            self.control.send_replace(state);
+        }
+    }
+
+    /// Drain pending events; returns the items that finished since the last poll
+    pub fn poll(&mut self) -> Vec<ItemOutcome> {
+        let mut outcomes = Vec::new();
+        let Some(receiver) = self.receiver.as_mut() else {
+            return outcomes;
+        };
+
+        loop {
+            match receiver.try_recv() {
+                Ok(JobEvent::ItemStarted(key)) => {
+                    self.progress.in_flight += 1;
+                    self.progress.current_item = Some(key);
+                }
+                Ok(JobEvent::ItemFinished(outcome)) => {
+                    self.progress.in_flight = self.progress.in_flight.saturating_sub(1);
+                    if outcome.attempts > 1 {
+                        self.retried += 1;
+                    }
+                    if let Some(failure) = &outcome.failure {
+                        self.failed += 1;
+                        *self.failures.entry(failure.class).or_default() += 1;
+                    } else {
+                        self.succeeded += 1;
+                    }
+                    self.progress.current = self.succeeded + self.failed;
+                    if let Some(journal) = self.journal.as_mut() {
+                        journal.record(&outcome);
+                    }
+                    outcomes.push(outcome);
+                }
+                Ok(JobEvent::ItemSkipped(object)) => {
+                    self.skipped.push(object);
+                }
+                Ok(JobEvent::Finished) => {
+                    self.status = if self.skipped.is_empty() {
+                        JobStatus::Completed
+                    } else {
+                        JobStatus::Cancelled
+                    };
+                    self.finish();
+                    break;
+                }
+                Err(TryRecvError::Empty) => {
+                    if let Some(journal) = self.journal.as_mut() {
+                        let _ = journal.flush();
+                    }
+                    break;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = JobStatus::Failed("job task stopped unexpectedly".into());
+                    self.finish();
+                    break;
+                }
+            }
+        }
+        outcomes
+    }
+
+    pub fn summary(&self) -> String {
+        match &self.status {
+            JobStatus::Running if self.is_paused() => format!(
+                "{} paused: {} / {} objects",
+                self.progress.operation, self.progress.current, self.progress.total
+            ),
+            JobStatus::Running => format!(
+                "{}: {} / {} objects",
+                self.progress.operation, self.progress.current, self.progress.total
+            ),
+            JobStatus::Cancelled => format!(
+                "{} cancelled: {} succeeded, {} failed, {} not started",
+                self.progress.operation,
+                self.succeeded,
+                self.failed,
+                self.skipped.len()
+            ),
+            JobStatus::Completed if self.failed > 0 => format!(
+                "{} complete: {} succeeded, {} failed ({})",
+                self.progress.operation,
+                self.succeeded,
+                self.failed,
+                self.failure_breakdown()
+            ),
+            JobStatus::Completed => format!(
+                "{} complete: {} objects succeeded",
+                self.progress.operation, self.succeeded
+            ),
+            JobStatus::Failed(err) => format!("{} failed: {}", self.progress.operation, err),
+        }
+    }
+
+    /// Per-class failure counts, e.g. "NoSuchKey: 2, AccessDenied: 1"
+    pub fn failure_breakdown(&self) -> String {
+        self.failures
+            .iter()
+            .map(|(class, count)| format!("{class}: {count}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn finish(&mut self) {
+        // Only a job where every item succeeded has nothing left to resume; failed or
+        // skipped items keep the journal around for a resume
+        if self.status == JobStatus::Completed && self.failed == 0 && self.skipped.is_empty() {
+            if let Some(journal) = self.journal.take() {
+                let _ = journal.remove();
+            }
+        } else if let Some(journal) = self.journal.as_mut() {
+            let _ = journal.save();
+        }
+        self.progress.in_flight = 0;
+        self.progress.current_item = None;
+        self.receiver = None;
+        self.handle = None;
+    }
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+        // Keep the outcomes seen so far when the app quits mid-job
+        if let Some(journal) = self.journal.as_mut() {
+            let _ = journal.save();
+        }
+    }
+}
+
+/// Spaces request starts evenly so a job stays under a requests-per-second ceiling
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(max_requests_per_second: Option<u32>) -> Option<Self> {
+        let rate = max_requests_per_second.filter(|rate| *rate > 0)?;
+        Some(Self {
+            interval: Duration::from_secs(1) / rate,
+            next_slot: Mutex::new(Instant::now()),
+        })
+    }
+
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+async fn run_job(
+    s3: S3Service,
+    bucket: String,
+    action: PendingAction,
+    objects: Vec<ObjectInfo>,
+    settings: Settings,
+    control: watch::Receiver<JobControl>,
+    tx: UnboundedSender<JobEvent>,
+) {
+    let limiter = RateLimiter::new(settings.max_requests_per_second);
+    let retry = RetryPolicy::with_max_attempts(settings.retry_attempts);
+
+    stream::iter(objects)
+        .for_each_concurrent(settings.concurrency(), |object| {
+            let (s3, bucket, action, limiter, retry, tx) =
+                (&s3, &bucket, &action, &limiter, &retry, &tx);
+            let mut control = control.clone();
+            async move {
+                if wait_while_paused(&mut control).await == JobControl::Cancel {
+                    let _ = tx.send(JobEvent::ItemSkipped(object));
+                    return;
+                }
+                let key = object.key;
+                let _ = tx.send(JobEvent::ItemStarted(key.clone()));
+
+                // Every attempt, including retries, counts against the rate limit
+                let result = retry
+                    .run(|| async {
+                        if let Some(limiter) = limiter {
+                            limiter.acquire().await;
+                        }
+                        match action {
+                            PendingAction::Transition { target_class } => {
+                                s3.transition_storage_class(bucket, &key, target_class.clone())
+                                    .await
+                            }
+                            PendingAction::Restore { days, tier } => {
+                                s3.request_restore(bucket, &key, *days, *tier).await
+                            }
+                        }
+                    })
+                    .await;
+
+                let outcome = match result {
+                    Ok(((), attempts)) => ItemOutcome {
+                        key,
+                        attempts,
+                        failure: None,
+                    },
+                    Err(failure) => {
+                        let message = match action {
+                            PendingAction::Transition { .. } => format!("{:#}", failure.error),
+                            PendingAction::Restore { .. } => describe_restore_error(&failure.error),
+                        };
+                        ItemOutcome {
+                            key,
+                            attempts: failure.attempts,
+                            failure: Some(ItemFailure {
+                                class: failure.class,
+                                message,
+                            }),
+                        }
+                    }
+                };
+                let _ = tx.send(JobEvent::ItemFinished(outcome));
+            }
+        })
+        .await;
+
+    let _ = tx.send(JobEvent::Finished);
+}
+
+/// Block while the job is paused; returns the control state that released it
+async fn wait_while_paused(control: &mut watch::Receiver<JobControl>) -> JobControl {
+    loop {
+        let state = *control.borrow_and_update();
+        if state != JobControl::Pause {
+            return state;
+        }
+        if control.changed().await.is_err() {
+            return JobControl::Cancel;
+        }
+    }
+}
+
+pub fn describe_restore_error(err: &anyhow::Error) -> String {
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<RestoreObjectError>>() {
+        match sdk_err {
+            SdkError::ServiceError(err) => {
+                let service = err.err();
+                let code = service.meta().code().unwrap_or("ServiceError");
+                let message = service
+                    .message()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "no message provided".into());
+                let friendly = match FailureClass::from_code(code) {
+                    FailureClass::NoSuchKey => {
+                        "object was not found (mask may target stale keys or bucket differs)"
+                    }
+                    FailureClass::InvalidObjectState => {
+                        "object is already being restored or not eligible for this operation"
+                    }
+                    FailureClass::AccessDenied => {
+                        "access denied (check s3:RestoreObject permission and bucket policy)"
+                    }
+                    _ => return format!("{code}: {message}"),
+                };
+                return format!("{code}: {friendly}");
+            }
+            SdkError::DispatchFailure(err) => {
+                return format!("network/dispatch failure: {err:?}");
+            }
+            SdkError::TimeoutError(_) => {
+                return "request timed out; please retry".into();
+            }
+            SdkError::ResponseError(ctx) => {
+                return format!("response error: {ctx:?}");
+            }
+            _ => {}
+        }
+    }
+    format!("{err:#}")
+}
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::app::PendingAction;
+use crate::jobs::ItemOutcome;
+use crate::models::ObjectInfo;
+use crate::settings::config_dir;
+
+/// Rewriting a large journal on every event-loop tick is wasteful, so saves are batched
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum KeyOutcome {
+    Pending,
+    Done,
+    Failed { class: String, message: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalItem {
+    pub object: ObjectInfo,
+    pub outcome: KeyOutcome,
+}
+
+/// On-disk record of a bulk operation, so an interrupted run can be resumed
+/// without repeating the keys that already went through.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Journal {
+    pub id: String,
+    pub bucket: String,
+    pub action: PendingAction,
+    pub created_at: String,
+    pub items: Vec<JournalItem>,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+    #[serde(skip)]
+    dirty: bool,
+    #[serde(skip)]
+    last_flush: Option<Instant>,
+}
+
+impl Journal {
+    /// Create and persist a journal with every object marked pending
+    pub fn create(bucket: &str, action: &PendingAction, objects: &[ObjectInfo]) -> Result<Self> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let path = journal_dir()?.join(format!("{id}.json"));
+        let items = objects
+            .iter()
+            .map(|object| JournalItem {
+                object: object.clone(),
+                outcome: KeyOutcome::Pending,
+            })
+            .collect();
+        let mut journal = Self {
+            id,
+            bucket: bucket.to_string(),
+            action: action.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            items,
+            path,
+            index: HashMap::new(),
+            dirty: true,
+            last_flush: None,
+        };
+        journal.build_index();
+        journal.save()?;
+        Ok(journal)
+    }
+
+    /// Load every journal that still has keys left to process; unreadable files are skipped
+    pub fn load_unfinished() -> Result<Vec<Self>> {
+        let mut journals = Vec::new();
+        for entry in fs::read_dir(journal_dir()?)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(mut journal) = serde_json::from_str::<Journal>(&content) else {
+                continue;
+            };
+            journal.path = path;
+            journal.build_index();
+            if journal.remaining_count() > 0 {
+                journals.push(journal);
+            }
+        }
+        journals.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(journals)
+    }
+
+    pub fn done_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.outcome == KeyOutcome::Done)
+            .count()
+    }
+
+    /// Keys not yet marked done, including ones that failed last time
+    pub fn remaining_count(&self) -> usize {
+        self.items.len() - self.done_count()
+    }
+
+    pub fn remaining_objects(&self) -> Vec<ObjectInfo> {
+        self.items
+            .iter()
+            .filter(|item| item.outcome != KeyOutcome::Done)
+            .map(|item| item.object.clone())
+            .collect()
+    }
+
+    pub fn record(&mut self, outcome: &ItemOutcome) {
+        let Some(&idx) = self.index.get(&outcome.key) else {
+            return;
+        };
+        self.items[idx].outcome = match &outcome.failure {
+            None => KeyOutcome::Done,
+            Some(failure) => KeyOutcome::Failed {
+                class: failure.class.to_string(),
+                message: failure.message.clone(),
+            },
+        };
+        self.dirty = true;
+    }
+
+    /// Save if there are unsaved outcomes and the last save was long enough ago
+    pub fn flush(&mut self) -> Result<()> {
+        let due = self
+            .last_flush
+            .is_none_or(|last| last.elapsed() >= FLUSH_INTERVAL);
+        if self.dirty && due {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let json = serde_json::to_string(self)?;
+        // Write to a temporary file first so a crash mid-write leaves the old journal intact
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("failed to write journal {}", self.path.display()))?;
+        self.dirty = false;
+        self.last_flush = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Delete the journal file once nothing is left to resume
+    pub fn remove(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn build_index(&mut self) {
+        self.index = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (item.object.key.clone(), idx))
+            .collect();
+    }
+}
+
+fn journal_dir() -> Result<PathBuf> {
+    let dir = config_dir()?.join("journals");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
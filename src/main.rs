@@ -1,21 +1,87 @@
 mod app;
 mod aws;
+mod cli;
+mod dry_run;
+mod jobs;
+mod journal;
 mod mask;
+mod mask_expr;
+mod mask_library;
 mod models;
+mod notify;
+mod plan;
+mod pricing;
+mod retry;
+mod scan;
+mod settings;
 mod tracker;
 mod tui;
 
 use anyhow::Result;
+use clap::Parser;
 
 use app::App;
 use aws::S3Service;
+use cli::Cli;
+use journal::Journal;
+use mask_library::MaskLibrary;
+use pricing::PricingTable;
+use settings::Settings;
 use tracker::RestoreTracker;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // Like the pricing table and mask library, broken settings fall back to defaults
+    let (settings, settings_warning) = match Settings::load() {
+        Ok(settings) => (settings, None),
+        Err(err) => (
+            Settings::reset().unwrap_or_default(),
+            Some(format!(
+                "Settings unreadable, using defaults ({err:#}); the old file is kept as settings.json.invalid"
+            )),
+        ),
+    };
+    if let Some(command) = cli.command {
+        if let Some(warning) = &settings_warning {
+            eprintln!("Warning: {warning}");
+        }
+        if let Err(err) = cli::run(command, cli.json, cli.dry_run, &settings).await {
+            eprintln!("Error: {err:#}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let mut app = App::new();
+    app.settings = settings;
+    app.dry_run = cli.dry_run;
+    if let Some(warning) = &settings_warning {
+        app.push_status(warning);
+    }
+    // Prices only feed estimates, so a broken pricing.json shouldn't stop the TUI
+    app.pricing = match PricingTable::load() {
+        Ok(pricing) => pricing,
+        Err(err) => {
+            app.push_status(&format!(
+                "Pricing table unreadable, using the bundled prices ({err:#}); the old file is kept as pricing.json.invalid"
+            ));
+            PricingTable::reset().unwrap_or_default()
+        }
+    };
+    app.mask_library = match MaskLibrary::load() {
+        Ok(library) => library,
+        // A broken masks.json shouldn't keep the TUI from starting
+        Err(err) => {
+            app.push_status(&format!(
+                "Mask library unreadable, starting empty ({err:#}); the old file is kept as masks.json.invalid"
+            ));
+            MaskLibrary::reset().unwrap_or_default()
+        }
+    };
     let s3 = S3Service::new().await?;
     let tracker = RestoreTracker::new()?;
+    app.pending_journals = Journal::load_unfinished()?;
 
     // Set the initial region to the user's default AWS region
     if let Some(region) = s3.region() {
@@ -1,17 +1,25 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-use regex::RegexBuilder;
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 
-use crate::models::StorageClassTier;
+use crate::mask_expr::MaskExpr;
+use crate::models::{ObjectInfo, ObjectTags, StorageClassTier};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MaskKind {
     Prefix,
     Suffix,
     Contains,
     Regex,
+    /// Shell glob: `*` and `?` stay within one path segment, `**` crosses `/`
+    Glob,
+    /// The pattern is an AND/OR/NOT expression of clauses, see [`MaskExpr`]
+    Expression,
 }
 
 impl fmt::Display for MaskKind {
@@ -21,11 +29,273 @@ impl fmt::Display for MaskKind {
             MaskKind::Suffix => "Suffix",
             MaskKind::Contains => "Contains",
             MaskKind::Regex => "Regex",
+            MaskKind::Glob => "Glob",
+            MaskKind::Expression => "Expression",
         };
         f.write_str(label)
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SizeComparison {
+    Larger,
+    Smaller,
+}
+
+/// Object size predicate, written as `> 1 GiB` or `< 128 KiB`
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SizeFilter {
+    pub comparison: SizeComparison,
+    pub bytes: i64,
+}
+
+impl SizeFilter {
+    pub fn matches(&self, size: i64) -> bool {
+        match self.comparison {
+            SizeComparison::Larger => size > self.bytes,
+            SizeComparison::Smaller => size < self.bytes,
+        }
+    }
+}
+
+impl fmt::Display for SizeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.comparison {
+            SizeComparison::Larger => ">",
+            SizeComparison::Smaller => "<",
+        };
+        // Largest unit dividing the threshold exactly, so the text parses back unchanged
+        const UNITS: [(&str, i64); 8] = [
+            ("TiB", 1 << 40),
+            ("TB", 1_000_000_000_000),
+            ("GiB", 1 << 30),
+            ("GB", 1_000_000_000),
+            ("MiB", 1 << 20),
+            ("MB", 1_000_000),
+            ("KiB", 1 << 10),
+            ("KB", 1_000),
+        ];
+        match UNITS
+            .iter()
+            .find(|(_, unit_bytes)| self.bytes != 0 && self.bytes % unit_bytes == 0)
+        {
+            Some((unit, unit_bytes)) => write!(f, "{op} {} {unit}", self.bytes / unit_bytes),
+            None => write!(f, "{op} {} B", self.bytes),
+        }
+    }
+}
+
+impl FromStr for SizeFilter {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let (comparison, rest) = if let Some(rest) = input.strip_prefix('>') {
+            (SizeComparison::Larger, rest)
+        } else if let Some(rest) = input.strip_prefix('<') {
+            (SizeComparison::Smaller, rest)
+        } else {
+            return Err("size filter must start with > or <, e.g. \"> 1 GiB\"".to_string());
+        };
+        let rest = rest.trim();
+        let split = rest
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+            .unwrap_or(rest.len());
+        let (number, unit) = rest.split_at(split);
+        let value: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid size '{rest}'"))?;
+        let multiplier: f64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1.0,
+            "k" | "kib" => 1024.0,
+            "kb" => 1e3,
+            "m" | "mib" => 1024.0 * 1024.0,
+            "mb" => 1e6,
+            "g" | "gib" => 1024.0 * 1024.0 * 1024.0,
+            "gb" => 1e9,
+            "t" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            "tb" => 1e12,
+            other => return Err(format!("unknown size unit '{other}'")),
+        };
+        Ok(SizeFilter {
+            comparison,
+            bytes: (value * multiplier).round() as i64,
+        })
+    }
+}
+
+/// `last_modified` predicate: `> 90d` (older than 90 days), `< 7d` (newer than
+/// 7 days) or an inclusive date range `2023-01-01..2023-06-30` with either end open
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DateFilter {
+    OlderThan {
+        days: i64,
+    },
+    NewerThan {
+        days: i64,
+    },
+    Between {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+}
+
+impl DateFilter {
+    /// Objects without a known modification time never match
+    pub fn matches(&self, last_modified: Option<&str>) -> bool {
+        let Some(modified) = last_modified
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+        else {
+            return false;
+        };
+        match *self {
+            DateFilter::OlderThan { days } => Utc::now() - modified > chrono::Duration::days(days),
+            DateFilter::NewerThan { days } => Utc::now() - modified < chrono::Duration::days(days),
+            DateFilter::Between { from, to } => {
+                let date = modified.date_naive();
+                from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateFilter::OlderThan { days } => write!(f, "> {days}d"),
+            DateFilter::NewerThan { days } => write!(f, "< {days}d"),
+            DateFilter::Between { from, to } => {
+                if let Some(from) = from {
+                    write!(f, "{from}")?;
+                }
+                f.write_str("..")?;
+                if let Some(to) = to {
+                    write!(f, "{to}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for DateFilter {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let parse_days = |value: &str| -> Result<i64, String> {
+            let value = value.trim();
+            value
+                .strip_suffix('d')
+                .unwrap_or(value)
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number of days '{value}'"))
+        };
+        if let Some(rest) = input.strip_prefix('>') {
+            return Ok(DateFilter::OlderThan {
+                days: parse_days(rest)?,
+            });
+        }
+        if let Some(rest) = input.strip_prefix('<') {
+            return Ok(DateFilter::NewerThan {
+                days: parse_days(rest)?,
+            });
+        }
+        let Some((from, to)) = input.split_once("..") else {
+            return Err(
+                "expected > 90d, < 7d or a date range like 2023-01-01..2023-06-30".to_string(),
+            );
+        };
+        let parse_date = |value: &str| -> Result<Option<NaiveDate>, String> {
+            let value = value.trim();
+            if value.is_empty() {
+                return Ok(None);
+            }
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| format!("invalid date '{value}', expected YYYY-MM-DD"))
+        };
+        let (from, to) = (parse_date(from)?, parse_date(to)?);
+        if from.is_none() && to.is_none() {
+            return Err("date range needs a start and/or an end".to_string());
+        }
+        Ok(DateFilter::Between { from, to })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TagCondition {
+    Equals(String),
+    Exists,
+    Missing,
+}
+
+/// Tag predicate: `tag:retention=short`, `tag:project exists` or `tag:project missing`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TagFilter {
+    pub key: String,
+    pub condition: TagCondition,
+}
+
+impl TagFilter {
+    pub fn matches(&self, tags: &ObjectTags) -> bool {
+        match &self.condition {
+            TagCondition::Equals(value) => tags.get(&self.key) == Some(value),
+            TagCondition::Exists => tags.contains_key(&self.key),
+            TagCondition::Missing => !tags.contains_key(&self.key),
+        }
+    }
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.condition {
+            TagCondition::Equals(value) => write!(f, "tag:{}={}", self.key, value),
+            TagCondition::Exists => write!(f, "tag:{} exists", self.key),
+            TagCondition::Missing => write!(f, "tag:{} missing", self.key),
+        }
+    }
+}
+
+impl FromStr for TagFilter {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let input = input.strip_prefix("tag:").unwrap_or(input).trim();
+        let (key, condition) = if let Some((key, value)) = input.split_once('=') {
+            (key, TagCondition::Equals(value.trim().to_string()))
+        } else if let Some(key) = input.strip_suffix(" exists") {
+            (key, TagCondition::Exists)
+        } else if let Some(key) = input.strip_suffix(" missing") {
+            (key, TagCondition::Missing)
+        } else {
+            return Err("expected tag:key=value, tag:key exists or tag:key missing".to_string());
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err("tag key cannot be empty".to_string());
+        }
+        Ok(TagFilter {
+            key: key.to_string(),
+            condition,
+        })
+    }
+}
+
+/// Why a mask pattern can't be used
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum MaskError {
+    #[error("invalid regex: {0}")]
+    Regex(String),
+    #[error("invalid glob: {0}")]
+    Glob(String),
+    #[error("invalid expression: {0}")]
+    Expression(String),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ObjectMask {
     pub name: String,
@@ -33,15 +303,151 @@ pub struct ObjectMask {
     pub kind: MaskKind,
     pub case_sensitive: bool,
     pub storage_class_filter: Option<StorageClassTier>,
+    #[serde(default)]
+    pub size_filter: Option<SizeFilter>,
+    #[serde(default)]
+    pub modified_filter: Option<DateFilter>,
+    #[serde(default)]
+    pub tag_filter: Option<TagFilter>,
+    /// Parsed form of `pattern` for `MaskKind::Expression`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<MaskExpr>,
+    /// Folder (key prefix ending in `/`) the mask is limited to, recursively. Compared
+    /// byte-for-byte like S3 prefixes, whatever `case_sensitive` says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Key matcher built from `pattern`, `kind` and `case_sensitive`. Masks made with
+    /// `new` compile it up front; deserialized masks compile it on first use.
+    #[serde(skip)]
+    matcher: OnceLock<Option<Matcher>>,
 }
 
 impl ObjectMask {
+    /// Build a mask without extra filters, compiling its pattern once
+    pub fn new(
+        name: String,
+        pattern: String,
+        kind: MaskKind,
+        case_sensitive: bool,
+    ) -> Result<Self, MaskError> {
+        let expression = match kind {
+            MaskKind::Expression => {
+                Some(pattern.parse::<MaskExpr>().map_err(MaskError::Expression)?)
+            }
+            _ => None,
+        };
+        let matcher = Matcher::compile(&kind, &pattern, expression.as_ref(), case_sensitive)?;
+        Ok(Self {
+            name,
+            pattern,
+            kind,
+            case_sensitive,
+            storage_class_filter: None,
+            size_filter: None,
+            modified_filter: None,
+            tag_filter: None,
+            expression,
+            scope: None,
+            matcher: OnceLock::from(Some(matcher)),
+        })
+    }
+
+    /// Check a pattern without building a mask, for feedback while typing
+    pub fn validate(kind: &MaskKind, pattern: &str, case_sensitive: bool) -> Result<(), MaskError> {
+        Self::new(
+            String::new(),
+            pattern.to_string(),
+            kind.clone(),
+            case_sensitive,
+        )
+        .map(|_| ())
+    }
+
+    fn matcher(&self) -> Option<&Matcher> {
+        self.matcher
+            .get_or_init(|| {
+                Matcher::compile(
+                    &self.kind,
+                    &self.pattern,
+                    self.expression.as_ref(),
+                    self.case_sensitive,
+                )
+                .ok()
+            })
+            .as_ref()
+    }
+
+    /// Invalid patterns (only possible in hand-edited files) match nothing
     pub fn matches(&self, key: &str) -> bool {
+        let in_scope = self
+            .scope
+            .as_deref()
+            .is_none_or(|scope| key.starts_with(scope));
+        in_scope
+            && self
+                .matcher()
+                .is_some_and(|matcher| matcher.matches(key, self.case_sensitive))
+    }
+
+    /// Evaluate every predicate, including tags. Objects whose tags haven't been
+    /// fetched never match a tag filter.
+    pub fn matches_object(&self, obj: &ObjectInfo) -> bool {
+        let tags_match = self
+            .tag_filter
+            .as_ref()
+            .is_none_or(|filter| obj.tags.as_ref().is_some_and(|tags| filter.matches(tags)));
+        tags_match && self.matches_without_tags(obj)
+    }
+
+    /// True when `matches_object` depends on tags fetched with GetObjectTagging
+    pub fn needs_tags(&self) -> bool {
+        self.tag_filter.is_some()
+    }
+
+    /// Evaluate the key pattern together with the storage class, size and date filters,
+    /// so tags only need fetching for objects that pass these
+    pub fn matches_without_tags(&self, obj: &ObjectInfo) -> bool {
+        let storage_matches = self
+            .storage_class_filter
+            .as_ref()
+            .map(|filter| &obj.storage_class == filter)
+            .unwrap_or(true); // If no filter, all storage classes match
+        let size_matches = self
+            .size_filter
+            .is_none_or(|filter| filter.matches(obj.size));
+        let modified_matches = self
+            .modified_filter
+            .is_none_or(|filter| filter.matches(obj.last_modified.as_deref()));
+        storage_matches && size_matches && modified_matches && self.matches(&obj.key)
+    }
+
+    /// Prefix that S3 can evaluate server-side via ListObjectsV2.
+    ///
+    /// Only case-sensitive prefix masks qualify, since S3 compares keys byte-for-byte.
+    /// Expressions qualify when every match must carry one of their prefix clauses.
+    /// A folder scope always qualifies; the pattern's prefix is used instead when it
+    /// lies inside the scope.
+    pub fn server_prefix(&self) -> Option<&str> {
+        let pattern_prefix = self.pattern_prefix();
+        match self.scope.as_deref().filter(|scope| !scope.is_empty()) {
+            Some(scope) if !pattern_prefix.is_some_and(|prefix| prefix.starts_with(scope)) => {
+                Some(scope)
+            }
+            _ => pattern_prefix,
+        }
+    }
+
+    fn pattern_prefix(&self) -> Option<&str> {
         match self.kind {
-            MaskKind::Regex => self.regex_match(key),
-            MaskKind::Prefix => normalized_cmp(self, key, Comparison::Prefix),
-            MaskKind::Suffix => normalized_cmp(self, key, Comparison::Suffix),
-            MaskKind::Contains => normalized_cmp(self, key, Comparison::Contains),
+            MaskKind::Prefix if self.case_sensitive && !self.pattern.is_empty() => {
+                Some(self.pattern.as_str())
+            }
+            MaskKind::Glob if self.case_sensitive => glob_literal_prefix(&self.pattern),
+            MaskKind::Expression if self.case_sensitive => self
+                .expression
+                .as_ref()
+                .and_then(|expr| expr.required_prefix()),
+            _ => None,
         }
     }
 
@@ -52,47 +458,385 @@ impl ObjectMask {
             format!("{} (insensitive)", self.pattern)
         };
 
-        let storage_filter = if let Some(ref storage) = self.storage_class_filter {
+        let mut storage_filter = if let Some(ref storage) = self.storage_class_filter {
             format!(" + {}", storage.label())
         } else {
             String::new()
         };
+        if let Some(size) = &self.size_filter {
+            storage_filter.push_str(&format!(" + size {size}"));
+        }
+        if let Some(modified) = &self.modified_filter {
+            storage_filter.push_str(&format!(" + modified {modified}"));
+        }
+        if let Some(tag) = &self.tag_filter {
+            storage_filter.push_str(&format!(" + {tag}"));
+        }
+        if let Some(scope) = &self.scope {
+            storage_filter.push_str(&format!(" in {scope}**"));
+        }
 
         format!(
             "{} ({:?}: {}{})",
             self.name, self.kind, pattern_display, storage_filter
         )
     }
+}
+
+/// Compiled form of a mask's key pattern
+#[derive(Clone, Debug)]
+enum Matcher {
+    /// Literal comparison; the pattern is already lowercased for case-insensitive masks
+    Literal(Comparison, String),
+    Regex(Regex),
+    And(Vec<Matcher>),
+    Or(Vec<Matcher>),
+    Not(Box<Matcher>),
+}
 
-    fn regex_match(&self, key: &str) -> bool {
-        RegexBuilder::new(&self.pattern)
-            .case_insensitive(!self.case_sensitive)
-            .build()
-            .map(|re| re.is_match(key))
-            .unwrap_or(false)
+impl Matcher {
+    fn compile(
+        kind: &MaskKind,
+        pattern: &str,
+        expression: Option<&MaskExpr>,
+        case_sensitive: bool,
+    ) -> Result<Self, MaskError> {
+        let literal = |comparison| {
+            let pattern = if case_sensitive {
+                pattern.to_string()
+            } else {
+                pattern.to_lowercase()
+            };
+            Ok(Matcher::Literal(comparison, pattern))
+        };
+        match kind {
+            MaskKind::Prefix => literal(Comparison::Prefix),
+            MaskKind::Suffix => literal(Comparison::Suffix),
+            MaskKind::Contains => literal(Comparison::Contains),
+            MaskKind::Regex => build_regex(pattern, case_sensitive)
+                .map(Matcher::Regex)
+                .map_err(MaskError::Regex),
+            MaskKind::Glob => build_regex(&glob_to_regex(pattern), case_sensitive)
+                .map(Matcher::Regex)
+                .map_err(MaskError::Glob),
+            MaskKind::Expression => {
+                let parsed;
+                let expr = match expression {
+                    Some(expr) => expr,
+                    None => {
+                        parsed = pattern.parse::<MaskExpr>().map_err(MaskError::Expression)?;
+                        &parsed
+                    }
+                };
+                Self::compile_expr(expr, case_sensitive)
+            }
+        }
     }
+
+    fn compile_expr(expr: &MaskExpr, case_sensitive: bool) -> Result<Self, MaskError> {
+        let all = |exprs: &[MaskExpr]| {
+            exprs
+                .iter()
+                .map(|expr| Self::compile_expr(expr, case_sensitive))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        match expr {
+            MaskExpr::Clause(clause) => {
+                Self::compile(&clause.kind, &clause.pattern, None, case_sensitive)
+                    .map_err(|err| MaskError::Expression(format!("{expr}: {err}")))
+            }
+            MaskExpr::And(exprs) => all(exprs).map(Matcher::And),
+            MaskExpr::Or(exprs) => all(exprs).map(Matcher::Or),
+            MaskExpr::Not(expr) => {
+                Self::compile_expr(expr, case_sensitive).map(|m| Matcher::Not(Box::new(m)))
+            }
+        }
+    }
+
+    fn matches(&self, key: &str, case_sensitive: bool) -> bool {
+        match self {
+            Matcher::Literal(comparison, pattern) => {
+                let key = if case_sensitive {
+                    Cow::Borrowed(key)
+                } else {
+                    Cow::Owned(key.to_lowercase())
+                };
+                match comparison {
+                    Comparison::Prefix => key.starts_with(pattern.as_str()),
+                    Comparison::Suffix => key.ends_with(pattern.as_str()),
+                    Comparison::Contains => key.contains(pattern.as_str()),
+                }
+            }
+            Matcher::Regex(re) => re.is_match(key),
+            Matcher::And(matchers) => matchers.iter().all(|m| m.matches(key, case_sensitive)),
+            Matcher::Or(matchers) => matchers.iter().any(|m| m.matches(key, case_sensitive)),
+            Matcher::Not(matcher) => !matcher.matches(key, case_sensitive),
+        }
+    }
+}
+
+/// Compile a regex, condensing the multi-line parse error to its last line
+fn build_regex(pattern: &str, case_sensitive: bool) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|err| {
+            let message = err.to_string();
+            message
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| line.trim().trim_start_matches("error: ").to_string())
+                .unwrap_or(message)
+        })
 }
 
+/// Translate a glob into an anchored regex.
+///
+/// `*` matches within a path segment, `**` across segments (`a/**/b` also matches
+/// `a/b`), `?` a single non-`/` character and `[...]`/`[!...]` a character class.
+pub fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.next_if_eq(&'/').is_some() {
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                // The text after `[` as written, for when the class never closes
+                let mut raw = String::new();
+                let mut closed = false;
+                if let Some(negation) = chars.next_if(|next| *next == '!' || *next == '^') {
+                    // Negated classes still never match the path separator
+                    class.push_str("^/");
+                    raw.push(negation);
+                }
+                // A `]` right after the opening bracket is literal
+                if chars.next_if_eq(&']').is_some() {
+                    class.push_str("\\]");
+                    raw.push(']');
+                }
+                for next in chars.by_ref() {
+                    if next == ']' {
+                        closed = true;
+                        break;
+                    }
+                    raw.push(next);
+                    if matches!(next, '\\' | '[' | '&' | '~') {
+                        class.push('\\');
+                    }
+                    class.push(next);
+                }
+                if closed {
+                    regex.push('[');
+                    regex.push_str(&class);
+                    regex.push(']');
+                } else {
+                    // Unclosed bracket: treat it literally
+                    regex.push_str("\\[");
+                    regex.push_str(&regex::escape(&raw));
+                }
+            }
+            _ => regex.push_str(&regex::escape(ch.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+/// The literal text before the first wildcard, which every match starts with
+pub fn glob_literal_prefix(pattern: &str) -> Option<&str> {
+    let end = pattern.find(['*', '?', '[']).unwrap_or(pattern.len());
+    Some(&pattern[..end]).filter(|prefix| !prefix.is_empty())
+}
+
+#[derive(Clone, Copy, Debug)]
 enum Comparison {
     Prefix,
     Suffix,
     Contains,
 }
 
-fn normalized<'a>(mask: &ObjectMask, input: &'a str) -> Cow<'a, str> {
-    if mask.case_sensitive {
-        Cow::Borrowed(input)
-    } else {
-        Cow::Owned(input.to_lowercase())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_filters_parse() {
+        use SizeComparison::*;
+        let cases = [
+            ("> 1 GiB", Ok((Larger, 1 << 30))),
+            ("<128KiB", Ok((Smaller, 128 << 10))),
+            (">1.5 MB", Ok((Larger, 1_500_000))),
+            ("> 2g", Ok((Larger, 2 << 30))),
+            ("> 10 TB", Ok((Larger, 10_000_000_000_000))),
+            ("  < 512  ", Ok((Smaller, 512))),
+            ("> 100 b", Ok((Larger, 100))),
+            (
+                "1 GiB",
+                Err("size filter must start with > or <, e.g. \"> 1 GiB\""),
+            ),
+            ("> GiB", Err("invalid size 'GiB'")),
+            ("> 5 PB", Err("unknown size unit 'pb'")),
+        ];
+        for (input, expected) in cases {
+            let expected = expected
+                .map(|(comparison, bytes)| SizeFilter { comparison, bytes })
+                .map_err(str::to_string);
+            assert_eq!(input.parse::<SizeFilter>(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn size_filters_display_round_trips() {
+        for input in [
+            "> 1 GiB",
+            "< 1500 KB",
+            "> 3 MiB",
+            "< 1023 B",
+            "> 0 B",
+            "> 10 TB",
+        ] {
+            let filter: SizeFilter = input.parse().unwrap();
+            assert_eq!(filter.to_string(), input);
+            assert_eq!(filter.to_string().parse::<SizeFilter>(), Ok(filter));
+        }
     }
-}
 
-fn normalized_cmp(mask: &ObjectMask, key: &str, comparison: Comparison) -> bool {
-    let key = normalized(mask, key);
-    let pattern = normalized(mask, &mask.pattern);
-    match comparison {
-        Comparison::Prefix => key.starts_with(pattern.as_ref()),
-        Comparison::Suffix => key.ends_with(pattern.as_ref()),
-        Comparison::Contains => key.contains(pattern.as_ref()),
+    #[test]
+    fn date_filters_parse() {
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+        let cases = [
+            ("> 90d", Ok(DateFilter::OlderThan { days: 90 })),
+            ("<7d", Ok(DateFilter::NewerThan { days: 7 })),
+            ("> 30", Ok(DateFilter::OlderThan { days: 30 })),
+            (
+                "2023-01-01..2023-06-30",
+                Ok(DateFilter::Between {
+                    from: date("2023-01-01"),
+                    to: date("2023-06-30"),
+                }),
+            ),
+            (
+                "2023-01-01..",
+                Ok(DateFilter::Between {
+                    from: date("2023-01-01"),
+                    to: None,
+                }),
+            ),
+            (
+                " .. 2023-06-30 ",
+                Ok(DateFilter::Between {
+                    from: None,
+                    to: date("2023-06-30"),
+                }),
+            ),
+            ("> soon", Err("invalid number of days 'soon'")),
+            ("..", Err("date range needs a start and/or an end")),
+            (
+                "2023-13-01..",
+                Err("invalid date '2023-13-01', expected YYYY-MM-DD"),
+            ),
+            (
+                "90d",
+                Err("expected > 90d, < 7d or a date range like 2023-01-01..2023-06-30"),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                input.parse::<DateFilter>(),
+                expected.map_err(str::to_string),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn tag_filters_parse() {
+        let tag = |key: &str, condition| TagFilter {
+            key: key.to_string(),
+            condition,
+        };
+        let equals = |value: &str| TagCondition::Equals(value.to_string());
+        let cases = [
+            ("tag:retention=short", Ok(tag("retention", equals("short")))),
+            ("retention = short ", Ok(tag("retention", equals("short")))),
+            ("tag:empty=", Ok(tag("empty", equals("")))),
+            ("tag:a=b=c", Ok(tag("a", equals("b=c")))),
+            (
+                "tag:project exists",
+                Ok(tag("project", TagCondition::Exists)),
+            ),
+            ("project missing", Ok(tag("project", TagCondition::Missing))),
+            ("tag:=short", Err("tag key cannot be empty")),
+            (
+                "tag:project",
+                Err("expected tag:key=value, tag:key exists or tag:key missing"),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                input.parse::<TagFilter>(),
+                expected.map_err(str::to_string),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn glob_matches() {
+        let cases: &[(&str, &str, bool)] = &[
+            ("*.log", "app.log", true),
+            ("*.log", "logs/app.log", false),
+            ("**/*.log", "app.log", true),
+            ("**/*.log", "logs/2024/app.log", true),
+            ("logs/**", "logs/2024/01/app.log", true),
+            ("file?.txt", "file1.txt", true),
+            ("file?.txt", "file/.txt", false),
+            ("[abc].txt", "b.txt", true),
+            ("[abc].txt", "d.txt", false),
+            ("[!abc].txt", "d.txt", true),
+            ("[!abc].txt", "a.txt", false),
+            ("[!abc].txt", "/.txt", false),
+            ("[]].txt", "].txt", true),
+            ("[a-c]x", "bx", true),
+            ("a+b(1).txt", "a+b(1).txt", true),
+            ("a+b(1).txt", "aab1.txt", false),
+            // Unclosed brackets are literal text
+            ("foo[!bar", "foo[!bar", true),
+            ("foo[!bar", "fooz", false),
+            ("[]", "[]", true),
+            ("[]x", "[]x", true),
+            ("x[*", "x[*", true),
+            ("x[*", "x[abc", false),
+        ];
+        for &(pattern, key, expected) in cases {
+            let regex = Regex::new(&glob_to_regex(pattern))
+                .unwrap_or_else(|err| panic!("{pattern}: {err}"));
+            assert_eq!(regex.is_match(key), expected, "{pattern} vs {key}");
+        }
+    }
+
+    #[test]
+    fn glob_literal_prefixes() {
+        let cases = [
+            ("logs/2024/*.gz", Some("logs/2024/")),
+            ("raw/file?.csv", Some("raw/file")),
+            ("data/[ab]/x", Some("data/")),
+            ("plain/key.txt", Some("plain/key.txt")),
+            ("*.log", None),
+        ];
+        for (pattern, expected) in cases {
+            assert_eq!(glob_literal_prefix(pattern), expected, "{pattern}");
+        }
     }
 }
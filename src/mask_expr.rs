@@ -0,0 +1,436 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::mask::{MaskKind, glob_literal_prefix};
+
+/// A single key comparison inside an expression, e.g. `prefix:logs/`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MaskClause {
+    pub kind: MaskKind,
+    pub pattern: String,
+}
+
+/// Boolean combination of key clauses.
+///
+/// Written as `prefix:logs/ & !suffix:.gz` or `prefix:logs/ AND NOT suffix:.gz`.
+/// `NOT` binds tightest, then `AND`, then `OR`; parentheses group. A clause is
+/// `kind:pattern` with kind `prefix`, `suffix`, `contains`, `regex` or `glob` (a bare
+/// pattern means `contains`). Patterns containing spaces, `&`, `|` or parentheses
+/// must be quoted: `regex:"^(a|b)/"`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MaskExpr {
+    Clause(MaskClause),
+    And(Vec<MaskExpr>),
+    Or(Vec<MaskExpr>),
+    Not(Box<MaskExpr>),
+}
+
+impl MaskExpr {
+    /// A prefix every matching key must start with, usable for server-side listing
+    pub fn required_prefix(&self) -> Option<&str> {
+        match self {
+            MaskExpr::Clause(MaskClause {
+                kind: MaskKind::Prefix,
+                pattern,
+            }) if !pattern.is_empty() => Some(pattern.as_str()),
+            MaskExpr::Clause(MaskClause {
+                kind: MaskKind::Glob,
+                pattern,
+            }) => glob_literal_prefix(pattern),
+            // Any prefix clause of a conjunction constrains every match; take the longest
+            MaskExpr::And(exprs) => exprs
+                .iter()
+                .filter_map(|expr| expr.required_prefix())
+                .max_by_key(|prefix| prefix.len()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MaskExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, exprs: &[MaskExpr], op: &str| {
+            for (idx, expr) in exprs.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, " {op} ")?;
+                }
+                // Parenthesize nested groups so the text parses back to the same tree
+                if matches!(expr, MaskExpr::And(_) | MaskExpr::Or(_)) {
+                    write!(f, "({expr})")?;
+                } else {
+                    write!(f, "{expr}")?;
+                }
+            }
+            Ok(())
+        };
+        match self {
+            MaskExpr::Clause(clause) => {
+                let kind = clause.kind.to_string().to_lowercase();
+                if clause.pattern.is_empty()
+                    || clause
+                        .pattern
+                        .contains(|ch: char| ch.is_whitespace() || "&|()\"".contains(ch))
+                {
+                    let escaped = clause.pattern.replace('\\', "\\\\").replace('"', "\\\"");
+                    write!(f, "{kind}:\"{escaped}\"")
+                } else {
+                    write!(f, "{kind}:{}", clause.pattern)
+                }
+            }
+            MaskExpr::And(exprs) => join(f, exprs, "&"),
+            MaskExpr::Or(exprs) => join(f, exprs, "|"),
+            MaskExpr::Not(expr) => match expr.as_ref() {
+                MaskExpr::Clause(_) | MaskExpr::Not(_) => write!(f, "!{expr}"),
+                _ => write!(f, "!({expr})"),
+            },
+        }
+    }
+}
+
+impl FromStr for MaskExpr {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("expression is empty".to_string());
+        }
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+        };
+        let expr = parser.or()?;
+        if let Some(token) = parser.tokens.next() {
+            return Err(format!("unexpected {}", token.describe()));
+        }
+        Ok(expr)
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Clause(MaskClause),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+            Token::And => "AND".to_string(),
+            Token::Or => "OR".to_string(),
+            Token::Not => "NOT".to_string(),
+            Token::Clause(clause) => format!("'{}'", clause.pattern),
+        }
+    }
+}
+
+fn parse_kind(name: &str) -> Option<MaskKind> {
+    match name.to_ascii_lowercase().as_str() {
+        "prefix" => Some(MaskKind::Prefix),
+        "suffix" => Some(MaskKind::Suffix),
+        "contains" => Some(MaskKind::Contains),
+        "regex" => Some(MaskKind::Regex),
+        "glob" => Some(MaskKind::Glob),
+        _ => None,
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            _ if ch.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | '!' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Not,
+                });
+            }
+            '"' => tokens.push(Token::Clause(MaskClause {
+                kind: MaskKind::Contains,
+                pattern: read_pattern(&mut chars)?,
+            })),
+            '&' | '|' => {
+                chars.next();
+                // Accept `&&` and `||` as well
+                if chars.peek() == Some(&ch) {
+                    chars.next();
+                }
+                tokens.push(if ch == '&' { Token::And } else { Token::Or });
+            }
+            _ => {
+                let word = read_pattern(&mut chars)?;
+                let token = match word.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => {
+                        let clause = match word.split_once(':') {
+                            Some((kind, rest)) if parse_kind(kind).is_some() => {
+                                let pattern = if rest.is_empty() && chars.peek() == Some(&'"') {
+                                    read_pattern(&mut chars)?
+                                } else {
+                                    rest.to_string()
+                                };
+                                MaskClause {
+                                    kind: parse_kind(kind).unwrap(),
+                                    pattern,
+                                }
+                            }
+                            _ => MaskClause {
+                                kind: MaskKind::Contains,
+                                pattern: word,
+                            },
+                        };
+                        Token::Clause(clause)
+                    }
+                };
+                tokens.push(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read a quoted string (with `\"` and `\\` escapes) or a bare word
+fn read_pattern(chars: &mut Peekable<Chars<'_>>) -> Result<String, String> {
+    let mut word = String::new();
+    if chars.peek() == Some(&'"') {
+        chars.next();
+        loop {
+            match chars.next() {
+                Some('"') => return Ok(word),
+                // Only `\"` and `\\` are escapes, so regex classes like `\d` pass through
+                Some('\\') => match chars.next_if(|next| *next == '"' || *next == '\\') {
+                    Some(escaped) => word.push(escaped),
+                    None => word.push('\\'),
+                },
+                Some(ch) => word.push(ch),
+                None => break,
+            }
+        }
+        return Err("unterminated quoted pattern".to_string());
+    }
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() || "&|()".contains(ch) {
+            break;
+        }
+        // A quote right after `kind:` starts the quoted pattern
+        if ch == '"' && word.ends_with(':') {
+            break;
+        }
+        word.push(ch);
+        chars.next();
+    }
+    Ok(word)
+}
+
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<MaskExpr, String> {
+        let mut exprs = vec![self.and()?];
+        while self
+            .tokens
+            .next_if(|token| matches!(token, Token::Or))
+            .is_some()
+        {
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            MaskExpr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<MaskExpr, String> {
+        let mut exprs = vec![self.unary()?];
+        while self
+            .tokens
+            .next_if(|token| matches!(token, Token::And))
+            .is_some()
+        {
+            exprs.push(self.unary()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            MaskExpr::And(exprs)
+        })
+    }
+
+    fn unary(&mut self) -> Result<MaskExpr, String> {
+        match self.tokens.next() {
+            Some(Token::Not) => Ok(MaskExpr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.tokens.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Clause(clause)) => Ok(MaskExpr::Clause(clause)),
+            Some(other) => Err(format!("unexpected {}", other.describe())),
+            None => Err("expression ends early".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clause(kind: MaskKind, pattern: &str) -> MaskExpr {
+        MaskExpr::Clause(MaskClause {
+            kind,
+            pattern: pattern.to_string(),
+        })
+    }
+
+    fn not(expr: MaskExpr) -> MaskExpr {
+        MaskExpr::Not(Box::new(expr))
+    }
+
+    #[test]
+    fn parses() {
+        use MaskKind::*;
+        let cases = [
+            ("logs", clause(Contains, "logs")),
+            ("prefix:logs/", clause(Prefix, "logs/")),
+            ("SUFFIX:.gz", clause(Suffix, ".gz")),
+            ("weird:thing", clause(Contains, "weird:thing")),
+            (
+                "prefix:logs/ & !suffix:.gz",
+                MaskExpr::And(vec![clause(Prefix, "logs/"), not(clause(Suffix, ".gz"))]),
+            ),
+            (
+                "prefix:a AND NOT suffix:b",
+                MaskExpr::And(vec![clause(Prefix, "a"), not(clause(Suffix, "b"))]),
+            ),
+            (
+                "a || b",
+                MaskExpr::Or(vec![clause(Contains, "a"), clause(Contains, "b")]),
+            ),
+            (r#"regex:"^(raw|staged)/""#, clause(Regex, "^(raw|staged)/")),
+            (r#""two words""#, clause(Contains, "two words")),
+            (r#"regex:"\d+\.log""#, clause(Regex, r"\d+\.log")),
+            (r#"contains:"say \"hi\"""#, clause(Contains, r#"say "hi""#)),
+            ("!!a", not(not(clause(Contains, "a")))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<MaskExpr>(), Ok(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn precedence_and_parentheses() {
+        use MaskKind::Contains;
+        let (a, b, c) = (
+            clause(Contains, "a"),
+            clause(Contains, "b"),
+            clause(Contains, "c"),
+        );
+        let cases = [
+            // AND binds tighter than OR
+            (
+                "a | b & c",
+                MaskExpr::Or(vec![a.clone(), MaskExpr::And(vec![b.clone(), c.clone()])]),
+            ),
+            (
+                "a & b | c",
+                MaskExpr::Or(vec![MaskExpr::And(vec![a.clone(), b.clone()]), c.clone()]),
+            ),
+            // NOT binds tighter than AND
+            ("!a & b", MaskExpr::And(vec![not(a.clone()), b.clone()])),
+            (
+                "(a | b) & c",
+                MaskExpr::And(vec![MaskExpr::Or(vec![a.clone(), b.clone()]), c.clone()]),
+            ),
+            ("!(a | b)", not(MaskExpr::Or(vec![a.clone(), b.clone()]))),
+            ("((a))", a.clone()),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<MaskExpr>(), Ok(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        let cases = [
+            ("", "expression is empty"),
+            ("   ", "expression is empty"),
+            (r#"regex:"^abc"#, "unterminated quoted pattern"),
+            (r#""open"#, "unterminated quoted pattern"),
+            ("(a | b", "missing ')'"),
+            ("a | b)", "unexpected ')'"),
+            ("a &", "expression ends early"),
+            ("& a", "unexpected AND"),
+            ("a b", "unexpected 'b'"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                input.parse::<MaskExpr>(),
+                Err(expected.to_string()),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let cases = [
+            "contains:logs",
+            "prefix:logs/ & !suffix:.gz",
+            "prefix:a | (suffix:b & contains:c)",
+            "(prefix:a | suffix:b) & contains:c",
+            "!(prefix:a & suffix:b)",
+            "!!contains:a",
+            r#"regex:"^(raw|staged)/""#,
+            r#"contains:"two words""#,
+            r#"contains:"say \"hi\"""#,
+            r#"contains:"back \\ slash""#,
+            r#"prefix:"""#,
+            "glob:logs/**/*.gz",
+        ];
+        for input in cases {
+            let expr: MaskExpr = input.parse().unwrap();
+            assert_eq!(expr.to_string(), input);
+            assert_eq!(expr.to_string().parse::<MaskExpr>(), Ok(expr), "{input}");
+        }
+    }
+
+    #[test]
+    fn required_prefixes() {
+        let cases = [
+            ("prefix:logs/", Some("logs/")),
+            ("prefix:logs/ & suffix:.gz", Some("logs/")),
+            ("prefix:logs/ & prefix:logs/2024/", Some("logs/2024/")),
+            ("glob:raw/*.csv", Some("raw/")),
+            ("glob:raw/*.csv & prefix:raw/2024", Some("raw/2024")),
+            ("prefix:a | prefix:b", None),
+            ("!prefix:logs/", None),
+            ("suffix:.gz", None),
+            (r#"prefix:"""#, None),
+        ];
+        for (input, expected) in cases {
+            let expr: MaskExpr = input.parse().unwrap();
+            assert_eq!(expr.required_prefix(), expected, "{input}");
+        }
+    }
+}
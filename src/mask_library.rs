@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::mask::ObjectMask;
+use crate::settings::config_dir;
+
+/// A mask kept in the library under a name the user picked
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedMask {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Only offered for loading while this bucket is selected
+    #[serde(default)]
+    pub bucket: Option<String>,
+    pub mask: ObjectMask,
+}
+
+impl SavedMask {
+    pub fn usable_in(&self, bucket: Option<&str>) -> bool {
+        self.bucket.is_none() || self.bucket.as_deref() == bucket
+    }
+}
+
+/// Named masks persisted in `masks.json` next to `restore_requests.json`
+pub struct MaskLibrary {
+    file_path: PathBuf,
+    pub masks: Vec<SavedMask>,
+}
+
+impl Default for MaskLibrary {
+    fn default() -> Self {
+        Self {
+            file_path: PathBuf::from("masks.json"),
+            masks: Vec::new(),
+        }
+    }
+}
+
+impl MaskLibrary {
+    pub fn load() -> Result<Self> {
+        let file_path = config_dir()?.join("masks.json");
+        let masks = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("invalid mask library {}", file_path.display()))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { file_path, masks })
+    }
+
+    /// Start with an empty library, setting an unreadable `masks.json` aside as
+    /// `masks.json.invalid` so the next save doesn't overwrite it
+    pub fn reset() -> Result<Self> {
+        let file_path = config_dir()?.join("masks.json");
+        if file_path.exists() {
+            fs::rename(&file_path, file_path.with_extension("json.invalid"))?;
+        }
+        Ok(Self {
+            file_path,
+            masks: Vec::new(),
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.masks)?;
+        fs::write(&self.file_path, content)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.masks.iter().any(|saved| saved.name == name)
+    }
+
+    /// Add a mask, or replace the one with the same name; returns its index
+    pub fn upsert(&mut self, saved: SavedMask) -> Result<usize> {
+        let index = match self.masks.iter().position(|m| m.name == saved.name) {
+            Some(index) => {
+                self.masks[index] = saved;
+                index
+            }
+            None => {
+                self.masks.push(saved);
+                self.masks.len() - 1
+            }
+        };
+        self.save()?;
+        Ok(index)
+    }
+
+    /// Copy a mask under the first free "<name> (copy N)" name; returns the new index
+    pub fn duplicate(&mut self, index: usize) -> Result<usize> {
+        let mut copy = self.masks[index].clone();
+        let base = copy.name.clone();
+        copy.name = (1..)
+            .map(|n| {
+                if n == 1 {
+                    format!("{base} (copy)")
+                } else {
+                    format!("{base} (copy {n})")
+                }
+            })
+            .find(|name| !self.contains(name))
+            .unwrap();
+        self.masks.insert(index + 1, copy);
+        self.save()?;
+        Ok(index + 1)
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<SavedMask> {
+        let removed = self.masks.remove(index);
+        self.save()?;
+        Ok(removed)
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use aws_sdk_s3::types::{ObjectStorageClass, StorageClass};
+use aws_sdk_s3::types::{ObjectStorageClass, StorageClass, Tier};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BucketInfo {
@@ -16,6 +17,15 @@ pub struct TrackedRestoreRequest {
     pub requested_at: String, // ISO 8601 timestamp
     pub days: i32,
     pub current_status: RestoreState,
+    /// Missing for requests tracked before the tier was recorded
+    #[serde(default)]
+    pub tier: Option<RetrievalTier>,
+    /// When the poller first saw the restore finish (ISO 8601)
+    #[serde(default)]
+    pub completed_at: Option<String>,
+    /// When the restored copy expires (ISO 8601)
+    #[serde(default)]
+    pub expires_at: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,6 +35,18 @@ pub struct ObjectInfo {
     pub last_modified: Option<String>,
     pub storage_class: StorageClassTier,
     pub restore_state: Option<RestoreState>,
+    /// Object tags, only fetched when a tag mask needs them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<ObjectTags>,
+}
+
+pub type ObjectTags = BTreeMap<String, String>;
+
+/// Restore state of an object together with the expiry of its restored copy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RestoreStatus {
+    pub state: Option<RestoreState>,
+    pub expiry: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -86,6 +108,14 @@ impl StorageClassTier {
         ]
     }
 
+    /// Parse an API storage class name such as `GLACIER_IR`, ignoring case
+    pub fn from_label(label: &str) -> Option<StorageClassTier> {
+        Self::all_for_filter()
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(label))
+            .and_then(|(_, tier)| tier)
+    }
+
     pub fn label(&self) -> &str {
         match self {
             StorageClassTier::Standard => "STANDARD",
@@ -146,3 +176,70 @@ impl From<Option<StorageClass>> for StorageClassTier {
         }
     }
 }
+
+/// Glacier retrieval speed for a restore request
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RetrievalTier {
+    Expedited,
+    #[default]
+    Standard,
+    Bulk,
+}
+
+impl RetrievalTier {
+    pub const ALL: [RetrievalTier; 3] = [
+        RetrievalTier::Expedited,
+        RetrievalTier::Standard,
+        RetrievalTier::Bulk,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RetrievalTier::Expedited => "Expedited",
+            RetrievalTier::Standard => "Standard",
+            RetrievalTier::Bulk => "Bulk",
+        }
+    }
+
+    pub fn to_sdk(self) -> Tier {
+        match self {
+            RetrievalTier::Expedited => Tier::Expedited,
+            RetrievalTier::Standard => Tier::Standard,
+            RetrievalTier::Bulk => Tier::Bulk,
+        }
+    }
+
+    /// Typical time until a restore completes, per AWS documentation.
+    /// Returns None when the tier isn't offered for the storage class.
+    pub fn typical_completion(&self, class: &StorageClassTier) -> Option<&'static str> {
+        let deep_archive = matches!(class, StorageClassTier::GlacierDeepArchive);
+        match (self, deep_archive) {
+            (RetrievalTier::Expedited, false) => Some("1–5 minutes"),
+            (RetrievalTier::Expedited, true) => None,
+            (RetrievalTier::Standard, false) => Some("3–5 hours"),
+            (RetrievalTier::Standard, true) => Some("within 12 hours"),
+            (RetrievalTier::Bulk, false) => Some("5–12 hours"),
+            (RetrievalTier::Bulk, true) => Some("within 48 hours"),
+        }
+    }
+
+    /// Cycle to the next (or previous) tier, wrapping around
+    pub fn step(self, forward: bool) -> Self {
+        let idx = Self::ALL.iter().position(|tier| *tier == self).unwrap_or(0);
+        let len = Self::ALL.len();
+        let next = if forward {
+            (idx + 1) % len
+        } else {
+            (idx + len - 1) % len
+        };
+        Self::ALL[next]
+    }
+}
+
+/// Size in KB, right-aligned to a fixed width for the objects column;
+/// trim it for totals in messages
+pub fn format_size(size: i64) -> String {
+    const KB: f64 = 1024.0;
+    let kb = size as f64 / KB;
+    format!("{:>10.2} KB", kb)
+}
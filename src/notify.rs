@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+use std::process::Stdio;
+
+use anyhow::Result;
+use tokio::process::Command;
+
+use crate::tracker::StatusChange;
+
+/// Ring the terminal bell and post an OSC 9 desktop notification.
+/// Terminals without OSC 9 support ignore the escape sequence.
+pub fn terminal(message: &str) {
+    // Control characters would end the escape sequence early
+    let message: String = message.chars().filter(|c| !c.is_control()).collect();
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "\x07\x1b]9;{message}\x07");
+    let _ = stdout.flush();
+}
+
+/// Run the user's `notify_command` once per completed restore, with the bucket,
+/// key and expiry as `$1`, `$2` and `$3`. Commands run one after another in the
+/// background with their output discarded so they can't draw over the TUI.
+pub fn run_command(command: &str, completed: &[StatusChange]) -> Result<()> {
+    let invocations: Vec<[String; 3]> = completed
+        .iter()
+        .map(|change| {
+            [
+                change.bucket.clone(),
+                change.key.clone(),
+                change.expires_at.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    if invocations.is_empty() {
+        return Ok(());
+    }
+
+    let mut first = spawn(command, &invocations[0])?;
+    let command = command.to_string();
+    tokio::spawn(async move {
+        let _ = first.wait().await;
+        for args in &invocations[1..] {
+            if let Ok(mut child) = spawn(&command, args) {
+                let _ = child.wait().await;
+            }
+        }
+    });
+    Ok(())
+}
+
+fn spawn(command: &str, args: &[String; 3]) -> Result<tokio::process::Child> {
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .arg("bucket-brigade")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(child)
+}
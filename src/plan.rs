@@ -0,0 +1,181 @@
+use anyhow::{Context, Result, bail};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::app::PendingAction;
+use crate::aws::S3Service;
+use crate::mask::ObjectMask;
+use crate::models::{ObjectInfo, RestoreState, StorageClassTier};
+use crate::retry::{FailureClass, classify};
+use crate::settings::create_unique_file;
+
+const PLAN_VERSION: u32 = 1;
+
+/// An object as it looked when the plan was made
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlannedObject {
+    pub key: String,
+    pub size: i64,
+    pub storage_class: StorageClassTier,
+}
+
+/// A reviewed bulk action, saved from the TUI and applied later with `apply-plan`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Plan {
+    pub version: u32,
+    pub created_at: String,
+    pub bucket: String,
+    pub action: PendingAction,
+    /// Mask the targets were resolved from, kept for reviewers
+    #[serde(default)]
+    pub mask: Option<ObjectMask>,
+    pub objects: Vec<PlannedObject>,
+}
+
+impl Plan {
+    pub fn new(
+        bucket: String,
+        action: PendingAction,
+        mask: Option<ObjectMask>,
+        objects: &[ObjectInfo],
+    ) -> Self {
+        Self {
+            version: PLAN_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            bucket,
+            action,
+            mask,
+            objects: objects
+                .iter()
+                .map(|obj| PlannedObject {
+                    key: obj.key.clone(),
+                    size: obj.size,
+                    storage_class: obj.storage_class.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Write the plan as pretty JSON so it diffs well in review
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let stem = format!(
+            "plan-{}-{}",
+            self.bucket,
+            chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+        );
+        let (path, mut file) = create_unique_file(dir, &stem, "json")?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())
+            .with_context(|| format!("failed to write plan {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read plan {}", path.display()))?;
+        let plan: Plan = serde_json::from_str(&content)
+            .with_context(|| format!("invalid plan file {}", path.display()))?;
+        if plan.version != PLAN_VERSION {
+            bail!(
+                "plan {} has version {}, expected {}",
+                path.display(),
+                plan.version,
+                PLAN_VERSION
+            );
+        }
+        Ok(plan)
+    }
+}
+
+/// Why a planned key is left out when the plan is applied
+#[derive(Clone, Debug)]
+pub enum SkipReason {
+    Missing,
+    AlreadyInTarget,
+    StorageClassChanged {
+        planned: StorageClassTier,
+        current: StorageClassTier,
+    },
+    AlreadyRestoring,
+    AlreadyRestored,
+    CheckFailed(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Missing => f.write_str("object no longer exists"),
+            SkipReason::AlreadyInTarget => f.write_str("already in the target storage class"),
+            SkipReason::StorageClassChanged { planned, current } => write!(
+                f,
+                "storage class changed from {} to {} since the plan was made",
+                planned.label(),
+                current.label()
+            ),
+            SkipReason::AlreadyRestoring => f.write_str("restore already in progress"),
+            SkipReason::AlreadyRestored => f.write_str("already restored"),
+            SkipReason::CheckFailed(err) => write!(f, "could not check current state: {err}"),
+        }
+    }
+}
+
+/// Compare an object's current state with what the plan expected
+fn check(
+    action: &PendingAction,
+    planned: &PlannedObject,
+    current: &ObjectInfo,
+) -> Result<(), SkipReason> {
+    if let PendingAction::Transition { target_class } = action
+        && &current.storage_class == target_class
+    {
+        return Err(SkipReason::AlreadyInTarget);
+    }
+    if current.storage_class != planned.storage_class {
+        return Err(SkipReason::StorageClassChanged {
+            planned: planned.storage_class.clone(),
+            current: current.storage_class.clone(),
+        });
+    }
+    if let PendingAction::Restore { .. } = action {
+        match current.restore_state {
+            Some(RestoreState::InProgress { .. }) => return Err(SkipReason::AlreadyRestoring),
+            Some(RestoreState::Available) => return Err(SkipReason::AlreadyRestored),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// HEAD every planned key and split the plan into objects that are still safe to
+/// act on and keys to skip
+pub async fn revalidate(
+    s3: &S3Service,
+    plan: &Plan,
+    concurrency: usize,
+) -> (Vec<ObjectInfo>, Vec<(String, SkipReason)>) {
+    let results: Vec<(String, Result<ObjectInfo, SkipReason>)> = stream::iter(&plan.objects)
+        .map(|planned| async move {
+            let result = match s3.refresh_object(&plan.bucket, &planned.key).await {
+                Ok(current) => check(&plan.action, planned, &current).map(|()| current),
+                Err(err) if classify(&err) == FailureClass::NoSuchKey => Err(SkipReason::Missing),
+                Err(err) => Err(SkipReason::CheckFailed(format!("{err:#}"))),
+            };
+            (planned.key.clone(), result)
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let mut ready = Vec::new();
+    let mut skipped = Vec::new();
+    for (key, result) in results {
+        match result {
+            Ok(current) => ready.push(current),
+            Err(reason) => skipped.push((key, reason)),
+        }
+    }
+    (ready, skipped)
+}
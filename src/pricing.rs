@@ -0,0 +1,201 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::app::PendingAction;
+use crate::models::{ObjectInfo, StorageClassTier};
+use crate::settings::config_dir;
+
+const BUNDLED_PRICING: &str = include_str!("../assets/pricing.json");
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+/// S3 bills storage per month; minimum-duration charges are prorated per day
+const DAYS_PER_MONTH: f64 = 30.0;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct RestorePrice {
+    pub per_gb: f64,
+    pub per_1000: f64,
+}
+
+/// Prices for one region, keyed by storage class label (`STANDARD_IA`, `GLACIER`, ...)
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RegionPricing {
+    pub storage_gb_month: BTreeMap<String, f64>,
+    pub put_per_1000: BTreeMap<String, f64>,
+    /// Per-GB fee for reading from infrequent access classes
+    #[serde(default)]
+    pub retrieval_gb: BTreeMap<String, f64>,
+    /// Restore prices per storage class, then per retrieval tier label
+    #[serde(default)]
+    pub restore: BTreeMap<String, BTreeMap<String, RestorePrice>>,
+}
+
+/// Price list used for the estimates in the confirm popup. The bundled table can be
+/// replaced by `pricing.json` in the config directory.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PricingTable {
+    pub currency: String,
+    pub default_region: String,
+    #[serde(default)]
+    pub minimum_storage_days: BTreeMap<String, i64>,
+    pub regions: BTreeMap<String, RegionPricing>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        serde_json::from_str(BUNDLED_PRICING).expect("bundled pricing table is valid")
+    }
+}
+
+impl PricingTable {
+    pub fn load() -> Result<Self> {
+        let file_path = config_dir()?.join("pricing.json");
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&file_path)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid pricing file {}", file_path.display()))
+    }
+
+    /// Fall back to the bundled prices, setting an unreadable `pricing.json` aside as
+    /// `pricing.json.invalid`
+    pub fn reset() -> Result<Self> {
+        let file_path = config_dir()?.join("pricing.json");
+        if file_path.exists() {
+            fs::rename(&file_path, file_path.with_extension("json.invalid"))?;
+        }
+        Ok(Self::default())
+    }
+
+    /// Prices for `region`, falling back to the table's default region.
+    /// The returned name is the region the prices are actually for.
+    fn region<'a>(&'a self, region: Option<&'a str>) -> Option<(&'a str, &'a RegionPricing)> {
+        region
+            .and_then(|name| self.regions.get(name).map(|prices| (name, prices)))
+            .or_else(|| {
+                self.regions
+                    .get(&self.default_region)
+                    .map(|prices| (self.default_region.as_str(), prices))
+            })
+    }
+
+    /// Estimate what running `action` against `objects` would cost, as of `now`
+    pub fn estimate<'a>(
+        &self,
+        region: Option<&str>,
+        action: &PendingAction,
+        objects: impl IntoIterator<Item = &'a ObjectInfo>,
+        now: DateTime<Utc>,
+    ) -> Option<CostEstimate> {
+        let (priced_region, prices) = self.region(region)?;
+        let mut estimate = CostEstimate {
+            currency: self.currency.clone(),
+            region: priced_region.to_string(),
+            ..CostEstimate::default()
+        };
+
+        for obj in objects {
+            let gb = obj.size as f64 / BYTES_PER_GB;
+            let source = obj.storage_class.label();
+            match action {
+                PendingAction::Transition { target_class } => {
+                    let target = target_class.label();
+                    let (Some(from), Some(to), Some(put)) = (
+                        prices.storage_gb_month.get(source),
+                        prices.storage_gb_month.get(target),
+                        prices.put_per_1000.get(target),
+                    ) else {
+                        estimate.unpriced += 1;
+                        continue;
+                    };
+                    estimate.objects += 1;
+                    estimate.monthly_storage_delta += gb * (to - from);
+                    // CopyObject is billed as a PUT to the destination class
+                    estimate.requests += put / 1000.0;
+                    estimate.retrieval += gb * prices.retrieval_gb.get(source).unwrap_or(&0.0);
+                    if let Some(penalty) = self.early_deletion(obj, *from, now) {
+                        estimate.early_deletion += penalty;
+                        estimate.early_deletion_objects += 1;
+                    }
+                }
+                PendingAction::Restore { days, tier } => {
+                    let (Some(price), Some(standard)) = (
+                        prices
+                            .restore
+                            .get(source)
+                            .and_then(|tiers| tiers.get(tier.label())),
+                        prices
+                            .storage_gb_month
+                            .get(StorageClassTier::Standard.label()),
+                    ) else {
+                        estimate.unpriced += 1;
+                        continue;
+                    };
+                    estimate.objects += 1;
+                    estimate.requests += price.per_1000 / 1000.0;
+                    estimate.retrieval += gb * price.per_gb;
+                    // The restored copy is billed at Standard rates while it exists
+                    estimate.restored_copy += gb * standard * f64::from(*days) / DAYS_PER_MONTH;
+                }
+            }
+        }
+        Some(estimate)
+    }
+
+    /// Charge for moving an object out of its class before the minimum storage
+    /// duration has passed, prorated over the remaining days
+    fn early_deletion(&self, obj: &ObjectInfo, price: f64, now: DateTime<Utc>) -> Option<f64> {
+        let minimum = *self.minimum_storage_days.get(obj.storage_class.label())?;
+        let modified = DateTime::parse_from_rfc3339(obj.last_modified.as_deref()?).ok()?;
+        let age = (now - modified.with_timezone(&Utc)).num_days();
+        let remaining = minimum - age;
+        if remaining <= 0 {
+            return None;
+        }
+        Some(obj.size as f64 / BYTES_PER_GB * price * remaining as f64 / DAYS_PER_MONTH)
+    }
+}
+
+/// Estimated charges for a bulk action
+#[derive(Clone, Debug, Default)]
+pub struct CostEstimate {
+    pub currency: String,
+    pub region: String,
+    pub objects: usize,
+    /// Objects whose storage class or tier isn't in the pricing table
+    pub unpriced: usize,
+    /// Change in the monthly storage bill after a transition (negative is a saving)
+    pub monthly_storage_delta: f64,
+    pub requests: f64,
+    pub early_deletion: f64,
+    pub early_deletion_objects: usize,
+    pub retrieval: f64,
+    /// Standard storage for restored copies over the requested number of days
+    pub restored_copy: f64,
+}
+
+impl CostEstimate {
+    /// One-off charges for running the action
+    pub fn one_off_total(&self) -> f64 {
+        self.requests + self.early_deletion + self.retrieval + self.restored_copy
+    }
+}
+
+/// e.g. "$1.23", "-$0.40", "<$0.01"
+pub fn format_cost(amount: f64, currency: &str) -> String {
+    let symbol = if currency == "USD" { "$" } else { "" };
+    let suffix = if symbol.is_empty() {
+        format!(" {currency}")
+    } else {
+        String::new()
+    };
+    if amount != 0.0 && amount.abs() < 0.01 {
+        let sign = if amount < 0.0 { "-" } else { "" };
+        return format!("{sign}<{symbol}0.01{suffix}");
+    }
+    let sign = if amount < 0.0 { "-" } else { "" };
+    format!("{sign}{symbol}{:.2}{suffix}", amount.abs())
+}
@@ -0,0 +1,195 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::time::{Duration, Instant};
+
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadError;
+use aws_sdk_s3::operation::copy_object::CopyObjectError;
+use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
+use aws_sdk_s3::operation::get_object_tagging::GetObjectTaggingError;
+use aws_sdk_s3::operation::head_object::HeadObjectError;
+use aws_sdk_s3::operation::restore_object::RestoreObjectError;
+use aws_sdk_s3::operation::upload_part_copy::UploadPartCopyError;
+
+/// Why a per-object request failed, used for retry decisions and job summaries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FailureClass {
+    Throttled,
+    /// Transient S3-side fault (`InternalError`, HTTP 500)
+    ServerError,
+    Timeout,
+    Dispatch,
+    NoSuchKey,
+    InvalidObjectState,
+    AccessDenied,
+    Other,
+}
+
+impl FailureClass {
+    /// Map an S3 error code onto a failure class
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "SlowDown"
+            | "Throttling"
+            | "ThrottlingException"
+            | "RequestLimitExceeded"
+            | "ServiceUnavailable"
+            | "TooManyRequests" => FailureClass::Throttled,
+            "InternalError" => FailureClass::ServerError,
+            "RequestTimeout" => FailureClass::Timeout,
+            // HeadObject has no response body, so a missing key only surfaces as NotFound
+            "NoSuchKey" | "NotFound" => FailureClass::NoSuchKey,
+            "InvalidObjectState" => FailureClass::InvalidObjectState,
+            "AccessDenied" => FailureClass::AccessDenied,
+            _ => FailureClass::Other,
+        }
+    }
+
+    /// Transient failures worth retrying; the rest are permanent for this object
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            FailureClass::Throttled
+                | FailureClass::ServerError
+                | FailureClass::Timeout
+                | FailureClass::Dispatch
+        )
+    }
+}
+
+impl fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FailureClass::Throttled => "Throttled",
+            FailureClass::ServerError => "ServerError",
+            FailureClass::Timeout => "Timeout",
+            FailureClass::Dispatch => "Network",
+            FailureClass::NoSuchKey => "NoSuchKey",
+            FailureClass::InvalidObjectState => "InvalidObjectState",
+            FailureClass::AccessDenied => "AccessDenied",
+            FailureClass::Other => "Other",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Classify an error returned by one of the `S3Service` bulk operations
+pub fn classify(err: &anyhow::Error) -> FailureClass {
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<CopyObjectError>>() {
+        return classify_sdk(sdk_err);
+    }
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<RestoreObjectError>>() {
+        return classify_sdk(sdk_err);
+    }
+    // Every transition HEADs the source first to carry its encryption and checksum over
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<HeadObjectError>>() {
+        return classify_sdk(sdk_err);
+    }
+    // Requests made by the multipart copy used for objects over 5 GB
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<GetObjectTaggingError>>() {
+        return classify_sdk(sdk_err);
+    }
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<CreateMultipartUploadError>>() {
+        return classify_sdk(sdk_err);
+    }
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<UploadPartCopyError>>() {
+        return classify_sdk(sdk_err);
+    }
+    if let Some(sdk_err) = err.downcast_ref::<SdkError<CompleteMultipartUploadError>>() {
+        return classify_sdk(sdk_err);
+    }
+    FailureClass::Other
+}
+
+fn classify_sdk<E: ProvideErrorMetadata>(err: &SdkError<E>) -> FailureClass {
+    match err {
+        SdkError::ServiceError(ctx) => {
+            match ctx.raw().status().as_u16() {
+                503 => return FailureClass::Throttled,
+                500 => return FailureClass::ServerError,
+                _ => {}
+            }
+            FailureClass::from_code(ctx.err().code().unwrap_or_default())
+        }
+        SdkError::TimeoutError(_) => FailureClass::Timeout,
+        SdkError::DispatchFailure(_) => FailureClass::Dispatch,
+        _ => FailureClass::Other,
+    }
+}
+
+/// A request that still failed after the retry policy gave up
+pub struct Failure {
+    pub class: FailureClass,
+    pub error: anyhow::Error,
+    pub attempts: u32,
+}
+
+/// Exponential backoff with full jitter for transient S3 failures
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(20),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn with_max_attempts(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Run `operation` until it succeeds, fails permanently, or runs out of attempts.
+    /// Returns the number of attempts alongside the value.
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<(T, u32), Failure>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match operation().await {
+                Ok(value) => return Ok((value, attempt)),
+                Err(error) => {
+                    let class = classify(&error);
+                    if !class.is_retryable() || attempt >= self.max_attempts {
+                        return Err(Failure {
+                            class,
+                            error,
+                            attempts: attempt,
+                        });
+                    }
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                }
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        exponential.mul_f64(jitter_fraction())
+    }
+}
+
+/// Uniform value in [0, 1) without pulling in a random number crate
+fn jitter_fraction() -> f64 {
+    let bits = RandomState::new().hash_one(Instant::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::aws::S3Service;
+use crate::mask::ObjectMask;
+use crate::models::{ObjectInfo, ObjectTags};
+
+/// ListObjectsV2 returns at most 1000 keys per page
+const SCAN_PAGE_SIZE: i32 = 1000;
+
+pub enum ScanEvent {
+    Page {
+        listed: usize,
+        matches: Vec<ObjectInfo>,
+    },
+    Finished,
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScanStatus {
+    Running,
+    Complete,
+    Failed(String),
+}
+
+/// Bucket-wide evaluation of a mask.
+///
+/// The lazily loaded object list only ever holds a few pages, so bulk actions
+/// resolve their targets through a scan that walks every page in the background.
+pub struct FullScan {
+    pub bucket: String,
+    pub mask: ObjectMask,
+    pub pages: usize,
+    pub listed: usize,
+    pub targets: Vec<ObjectInfo>,
+    pub total_bytes: i64,
+    pub status: ScanStatus,
+    receiver: Option<UnboundedReceiver<ScanEvent>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FullScan {
+    /// Spawn a background task that lists every page of the bucket
+    pub fn start(s3: &S3Service, bucket: String, mask: ObjectMask) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(scan_bucket(s3.clone(), bucket.clone(), mask.clone(), tx));
+        Self {
+            bucket,
+            mask,
+            pages: 0,
+            listed: 0,
+            targets: Vec::new(),
+            total_bytes: 0,
+            status: ScanStatus::Running,
+            receiver: Some(rx),
+            handle: Some(handle),
+        }
+    }
+
+    /// Build an already complete scan from objects that are known to cover the whole bucket
+    pub fn from_loaded(bucket: String, mask: ObjectMask, objects: &[ObjectInfo]) -> Self {
+        let targets: Vec<ObjectInfo> = objects
+            .iter()
+            .filter(|obj| mask.matches_object(obj))
+            .cloned()
+            .collect();
+        let total_bytes = targets.iter().map(|obj| obj.size).sum();
+        Self {
+            bucket,
+            mask,
+            pages: 1,
+            listed: objects.len(),
+            targets,
+            total_bytes,
+            status: ScanStatus::Complete,
+            receiver: None,
+            handle: None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.status == ScanStatus::Complete
+    }
+
+    /// Drain pending events from the scan task; returns true when the scan just finished
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return false;
+        };
+
+        let mut finished = false;
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                ScanEvent::Page { listed, matches } => {
+                    self.pages += 1;
+                    self.listed += listed;
+                    self.total_bytes += matches.iter().map(|obj| obj.size).sum::<i64>();
+                    self.targets.extend(matches);
+                }
+                ScanEvent::Finished => {
+                    self.status = ScanStatus::Complete;
+                    finished = true;
+                }
+                ScanEvent::Failed(err) => {
+                    self.status = ScanStatus::Failed(err);
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.receiver = None;
+            self.handle = None;
+        }
+        finished
+    }
+}
+
+impl Drop for FullScan {
+    fn drop(&mut self) {
+        // Stop listing if the user cancelled before the scan finished
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+async fn scan_bucket(
+    s3: S3Service,
+    bucket: String,
+    mask: ObjectMask,
+    tx: UnboundedSender<ScanEvent>,
+) {
+    let prefix = mask.server_prefix().map(|p| p.to_string());
+    let mut continuation_token = None;
+    loop {
+        match s3
+            .list_objects_paginated(
+                &bucket,
+                prefix.as_deref(),
+                continuation_token,
+                SCAN_PAGE_SIZE,
+            )
+            .await
+        {
+            Ok((objects, next_token)) => {
+                let listed = objects.len();
+                let mut candidates: Vec<ObjectInfo> = objects
+                    .into_iter()
+                    .filter(|obj| mask.matches_without_tags(obj))
+                    .collect();
+                if mask.needs_tags() {
+                    let mut tags: HashMap<String, Option<ObjectTags>> = s3
+                        .batch_fetch_tags(&bucket, &candidates)
+                        .await
+                        .into_iter()
+                        .collect();
+                    for obj in &mut candidates {
+                        obj.tags = tags.remove(&obj.key).flatten();
+                    }
+                }
+                let matches = candidates
+                    .into_iter()
+                    .filter(|obj| mask.matches_object(obj))
+                    .collect();
+                if tx.send(ScanEvent::Page { listed, matches }).is_err() {
+                    return;
+                }
+                match next_token {
+                    Some(token) => continuation_token = Some(token),
+                    None => break,
+                }
+            }
+            Err(err) => {
+                let _ = tx.send(ScanEvent::Failed(format!("{err:#}")));
+                return;
+            }
+        }
+    }
+    let _ = tx.send(ScanEvent::Finished);
+}
@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Directory holding persisted state such as `restore_requests.json` and `settings.json`
+pub fn config_dir() -> Result<PathBuf> {
+    let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    fs::create_dir_all(&config_dir)?;
+    Ok(config_dir)
+}
+
+/// Create `<stem>.<extension>` in `dir` without overwriting an existing file; names
+/// taken already (e.g. two writes in the same second) get a `-2`, `-3`, … suffix
+pub fn create_unique_file(dir: &Path, stem: &str, extension: &str) -> Result<(PathBuf, fs::File)> {
+    fs::create_dir_all(dir)?;
+    for attempt in 1u32.. {
+        let name = if attempt == 1 {
+            format!("{stem}.{extension}")
+        } else {
+            format!("{stem}-{attempt}.{extension}")
+        };
+        let path = dir.join(name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to create {}", path.display()));
+            }
+        }
+    }
+    unreachable!("ran out of file name suffixes")
+}
+
+/// User-tunable options loaded from `settings.json`; missing fields fall back to defaults
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Number of CopyObject/RestoreObject requests a job keeps in flight
+    pub concurrency: usize,
+    /// Optional ceiling on requests started per second by each job
+    pub max_requests_per_second: Option<u32>,
+    /// Attempts per object before a throttled or timed out request is reported as failed
+    pub retry_attempts: u32,
+    /// Seconds between checks of in-progress tracked restores
+    pub restore_poll_interval_secs: u64,
+    /// Shell command run for each completed restore with bucket, key and expiry as `$1`..`$3`
+    pub notify_command: Option<String>,
+    /// Where plan files saved from the confirm step go; defaults to the working directory
+    pub plan_dir: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            concurrency: 16,
+            max_requests_per_second: None,
+            retry_attempts: 5,
+            restore_poll_interval_secs: 300,
+            notify_command: None,
+            plan_dir: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Self> {
+        let file_path = config_dir()?.join("settings.json");
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&file_path)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid settings file {}", file_path.display()))
+    }
+
+    /// Fall back to the defaults, setting an unreadable `settings.json` aside as
+    /// `settings.json.invalid`
+    pub fn reset() -> Result<Self> {
+        let file_path = config_dir()?.join("settings.json");
+        if file_path.exists() {
+            fs::rename(&file_path, file_path.with_extension("json.invalid"))?;
+        }
+        Ok(Self::default())
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.max(1)
+    }
+
+    pub fn plan_dir(&self) -> PathBuf {
+        self.plan_dir.clone().unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    pub fn restore_poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.restore_poll_interval_secs.max(30))
+    }
+}
@@ -1,8 +1,25 @@
 use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use tokio::task::JoinHandle;
 
-use crate::models::{RestoreState, TrackedRestoreRequest};
+use crate::aws::S3Service;
+use crate::models::{RestoreState, RestoreStatus, RetrievalTier, TrackedRestoreRequest};
+use crate::settings::config_dir;
+
+/// (bucket, key, status) for every key a poll checked; status is None where HeadObject failed
+type PollResults = Vec<(String, String, Option<RestoreStatus>)>;
+
+/// A tracked request whose state changed during a poll
+#[derive(Clone, Debug)]
+pub struct StatusChange {
+    pub bucket: String,
+    pub key: String,
+    pub previous: RestoreState,
+    pub current: RestoreState,
+    pub expires_at: Option<String>,
+}
 
 pub struct RestoreTracker {
     file_path: PathBuf,
@@ -11,58 +28,127 @@ pub struct RestoreTracker {
 
 impl RestoreTracker {
     pub fn new() -> Result<Self> {
-        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
-            .map(|dirs| dirs.config_dir().to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."));
+        let file_path = config_dir()?.join("restore_requests.json");
 
-        fs::create_dir_all(&config_dir)?;
-        let file_path = config_dir.join("restore_requests.json");
-
-        let requests = if file_path.exists() {
+        let mut requests: Vec<TrackedRestoreRequest> = if file_path.exists() {
             let content = fs::read_to_string(&file_path)?;
             serde_json::from_str(&content).unwrap_or_default()
         } else {
             Vec::new()
         };
 
+        // Older files could hold several entries per key; keep the latest request
+        let mut seen = HashSet::new();
+        requests.reverse();
+        requests.retain(|r| seen.insert((r.bucket.clone(), r.key.clone())));
+        requests.reverse();
+
         Ok(Self {
             file_path,
             requests,
         })
     }
 
-    pub fn add_request(&mut self, bucket: String, key: String, days: i32) {
+    /// Track a batch of restore requests in one bucket and save once. A key that is
+    /// already tracked has its entry replaced by the new request.
+    pub fn add_requests(
+        &mut self,
+        bucket: &str,
+        keys: impl IntoIterator<Item = String>,
+        days: i32,
+        tier: RetrievalTier,
+    ) {
+        let keys: HashSet<String> = keys.into_iter().collect();
+        if keys.is_empty() {
+            return;
+        }
+        self.requests
+            .retain(|r| r.bucket != bucket || !keys.contains(&r.key));
+
         let now = chrono::Utc::now().to_rfc3339();
-        self.requests.push(TrackedRestoreRequest {
-            bucket,
-            key,
-            requested_at: now,
-            days,
-            current_status: RestoreState::InProgress { expiry: None },
-        });
+        self.requests
+            .extend(keys.into_iter().map(|key| TrackedRestoreRequest {
+                bucket: bucket.to_string(),
+                key,
+                requested_at: now.clone(),
+                days,
+                current_status: RestoreState::InProgress { expiry: None },
+                tier: Some(tier),
+                completed_at: None,
+                expires_at: None,
+            }));
         let _ = self.save();
     }
 
+    /// Apply the results of a poll and save once; returns the requests whose state changed
+    pub fn apply_poll(&mut self, results: PollResults) -> Vec<StatusChange> {
+        let mut changes: Vec<StatusChange> = results
+            .into_iter()
+            .filter_map(|(bucket, key, status)| self.update_status(&bucket, &key, status?))
+            .collect();
+        changes.extend(self.expire_elapsed());
+        let _ = self.save();
+        changes
+    }
+
     /// Update the status of a tracked restore request
-    #[allow(dead_code)]
-    pub fn update_status(&mut self, bucket: &str, key: &str, status: RestoreState) {
-        if let Some(req) = self
+    fn update_status(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        status: RestoreStatus,
+    ) -> Option<StatusChange> {
+        let req = self
             .requests
             .iter_mut()
-            .find(|r| r.bucket == bucket && r.key == key)
-        {
-            req.current_status = status.clone();
+            .find(|r| r.bucket == bucket && r.key == key)?;
+
+        // No restore header at all means the restored copy is gone
+        let current = status.state.unwrap_or(RestoreState::Expired);
+        if status.expiry.is_some() {
+            req.expires_at = status.expiry;
+        }
+        if current == RestoreState::Available && req.completed_at.is_none() {
+            req.completed_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        if req.current_status == current {
+            return None;
+        }
+        let previous = std::mem::replace(&mut req.current_status, current.clone());
+        Some(StatusChange {
+            bucket: req.bucket.clone(),
+            key: req.key.clone(),
+            previous,
+            current,
+            expires_at: req.expires_at.clone(),
+        })
+    }
 
-            // Remove completed requests after they've been available for a while
-            if matches!(status, RestoreState::Available) {
-                // Could add logic here to remove old available requests
+    /// Restored copies past their expiry date are expired without needing a HEAD request
+    fn expire_elapsed(&mut self) -> Vec<StatusChange> {
+        let now = chrono::Utc::now();
+        let mut changes = Vec::new();
+        for req in &mut self.requests {
+            let elapsed = req
+                .expires_at
+                .as_deref()
+                .and_then(|expiry| chrono::DateTime::parse_from_rfc3339(expiry).ok())
+                .is_some_and(|expiry| expiry < now);
+            if elapsed && req.current_status == RestoreState::Available {
+                req.current_status = RestoreState::Expired;
+                changes.push(StatusChange {
+                    bucket: req.bucket.clone(),
+                    key: req.key.clone(),
+                    previous: RestoreState::Available,
+                    current: RestoreState::Expired,
+                    expires_at: req.expires_at.clone(),
+                });
             }
         }
-        let _ = self.save();
+        changes
     }
 
     /// Get only active (in-progress) restore requests
-    #[allow(dead_code)]
     pub fn get_active_requests(&self) -> Vec<TrackedRestoreRequest> {
         self.requests
             .iter()
@@ -81,7 +167,6 @@ impl RestoreTracker {
     }
 
     /// Remove completed or expired restore requests from tracking
-    #[allow(dead_code)]
     pub fn remove_completed(&mut self) {
         self.requests.retain(|r| {
             !matches!(
@@ -98,3 +183,56 @@ impl RestoreTracker {
         Ok(())
     }
 }
+
+/// Background HeadObject pass over the in-progress tracked requests
+pub struct RestorePoll {
+    handle: Option<JoinHandle<PollResults>>,
+}
+
+impl RestorePoll {
+    pub fn start(s3: &S3Service, requests: &[TrackedRestoreRequest]) -> Self {
+        let mut by_bucket: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for req in requests {
+            by_bucket
+                .entry(req.bucket.clone())
+                .or_default()
+                .push(req.key.clone());
+        }
+        let s3 = s3.clone();
+        let handle = tokio::spawn(async move {
+            let mut results = Vec::new();
+            for (bucket, keys) in by_bucket {
+                let statuses = s3.batch_refresh_restore_status(&bucket, &keys).await;
+                results.extend(
+                    statuses
+                        .into_iter()
+                        .map(|(key, status)| (bucket.clone(), key, status)),
+                );
+            }
+            results
+        });
+        Self {
+            handle: Some(handle),
+        }
+    }
+
+    /// Results of the poll once it has finished, without blocking while it runs
+    pub async fn take_results(&mut self) -> Option<PollResults> {
+        match self.handle.take() {
+            Some(handle) if !handle.is_finished() => {
+                self.handle = Some(handle);
+                None
+            }
+            Some(handle) => Some(handle.await.unwrap_or_default()),
+            None => Some(Vec::new()),
+        }
+    }
+}
+
+impl Drop for RestorePoll {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::{self, IsTerminal, Stdout};
 use std::time::Duration;
 
@@ -14,14 +16,25 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap};
 
-use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
-use aws_sdk_s3::operation::restore_object::RestoreObjectError;
-
-use crate::app::{ActivePane, App, AppMode, MaskEditorField, PendingAction, StorageIntent};
+use crate::app::{
+    ActivePane, App, AppMode, LibraryForm, LibraryFormField, MaskDraft, MaskEditorField,
+    PendingAction, StorageIntent,
+};
 use crate::aws::S3Service;
-use crate::mask::ObjectMask;
-use crate::models::{RestoreState, StorageClassTier};
-use crate::tracker::RestoreTracker;
+use crate::dry_run;
+use crate::jobs::{Job, JobId, JobStatus};
+use crate::journal::Journal;
+use crate::mask::{DateFilter, MaskKind, ObjectMask, SizeFilter, TagFilter};
+use crate::mask_library::SavedMask;
+use crate::models::{ObjectInfo, RestoreState, RetrievalTier, StorageClassTier, format_size};
+use crate::notify;
+use crate::plan::Plan;
+use crate::pricing::format_cost;
+use crate::scan::{FullScan, ScanStatus};
+use crate::tracker::{RestorePoll, RestoreTracker, StatusChange};
+
+const DEFAULT_RESTORE_DAYS: i32 = 7;
+const MAX_RESTORE_DAYS: i32 = 365;
 
 pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) -> Result<()> {
     // Verify we have a terminal before trying to initialize TUI
@@ -29,7 +42,8 @@ pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) ->
         anyhow::bail!(
             "This application requires a terminal to run.\n\
              stdout is not connected to a terminal (TTY).\n\
-             Please run this application directly in a terminal."
+             Please run this application directly in a terminal,\n\
+             or use a subcommand (see --help) for scripted use."
         );
     }
 
@@ -57,6 +71,12 @@ pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) ->
         }
     }
 
+    // Offer to pick up jobs that were interrupted last time
+    if app.mode != AppMode::CredentialError && !app.pending_journals.is_empty() {
+        app.journal_cursor = 0;
+        app.set_mode(AppMode::ResumingJournals);
+    }
+
     let result = event_loop(&mut terminal, app, s3, &mut tracker).await;
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -82,6 +102,8 @@ async fn event_loop(
             && last_change.elapsed() >= Duration::from_secs(1)
         {
             app.pending_bucket_load = false;
+            app.folder.clear();
+            app.clear_marks();
             if let Err(err) = load_objects_for_selection(app, s3).await {
                 app.push_status(&format!("Failed to load objects: {err:#}"));
             } else {
@@ -90,6 +112,14 @@ async fn event_loop(
             }
         }
 
+        // Re-list when the active mask changed the server-side prefix
+        if app.listing_is_stale()
+            && !app.is_loading_objects
+            && let Err(err) = load_objects_for_selection(app, s3).await
+        {
+            app.push_status(&format!("Failed to load objects: {err:#}"));
+        }
+
         // Check if we should lazy-load more objects
         if app.should_load_more()
             && !app.is_loading_objects
@@ -98,9 +128,22 @@ async fn event_loop(
             app.push_status(&format!("Failed to load more: {err:#}"));
         }
 
-        // Check if it's time to auto-refresh
+        // Collect pages from a running bucket-wide target scan
+        poll_target_scan(app);
+
+        // Apply progress from background transition/restore jobs
+        poll_jobs(app, s3, tracker).await;
+
+        // Check tracked restores in the background and record what changed
+        poll_restore_tracker(app, s3, tracker).await;
+
+        // Check if it's time to auto-refresh; popups and editors work on the listing
+        // as it is, so only refresh while browsing
         if last_refresh.elapsed() >= refresh_interval {
-            if !app.objects.is_empty() && app.selected_bucket_name().is_some() {
+            if app.mode == AppMode::Browsing
+                && !app.objects.is_empty()
+                && app.selected_bucket_name().is_some()
+            {
                 // Silently refresh with pagination
                 let _ = load_objects_for_selection(app, s3).await;
             }
@@ -157,28 +200,56 @@ async fn handle_key_event(
             return Ok(false);
         }
         AppMode::ViewingRestoreRequests => {
-            if matches!(
-                key.code,
-                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('t') | KeyCode::Char('T')
-            ) {
-                app.set_mode(AppMode::Browsing);
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('t') | KeyCode::Char('T') => {
+                    app.set_mode(AppMode::Browsing);
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') => {
+                    let before = tracker.get_all_requests().len();
+                    tracker.remove_completed();
+                    let cleared = before - tracker.get_all_requests().len();
+                    app.push_status(&format!(
+                        "Cleared {} available or expired restore requests",
+                        cleared
+                    ));
+                }
+                _ => {}
             }
             return Ok(false);
         }
         AppMode::EditingMask => {
-            handle_mask_editor_keys(key, app);
+            handle_mask_editor_keys(key, app, s3).await;
+            if app.mode == AppMode::EditingMask {
+                refresh_mask_preview(app);
+            }
             return Ok(false);
         }
         AppMode::SelectingStorageClass => {
-            handle_storage_class_selector(key, app);
+            handle_storage_class_selector(key, app, s3);
             return Ok(false);
         }
         AppMode::Confirming => {
-            handle_confirmation_keys(key, app, s3, tracker).await?;
+            handle_confirmation_keys(key, app, s3)?;
             return Ok(false);
         }
         AppMode::ShowingProgress => {
-            // Ignore all key presses during progress operations
+            handle_progress_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::ViewingJobs => {
+            handle_jobs_popup_keys(key, app, s3);
+            return Ok(false);
+        }
+        AppMode::ResumingJournals => {
+            handle_journal_keys(key, app, s3);
+            return Ok(false);
+        }
+        AppMode::BrowsingMaskLibrary => {
+            handle_mask_library_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::NamingSavedMask => {
+            handle_library_form_keys(key, app);
             return Ok(false);
         }
         AppMode::Browsing => {}
@@ -192,31 +263,55 @@ async fn handle_key_event(
         KeyCode::BackTab => {
             app.previous_pane();
         }
+        // Shift+↑/↓ marks the rows between the anchor and the cursor
+        KeyCode::Up | KeyCode::Down
+            if key.modifiers.contains(KeyModifiers::SHIFT)
+                && app.active_pane == ActivePane::Objects =>
+        {
+            move_selection(app, if key.code == KeyCode::Up { -1 } else { 1 });
+            app.mark_range();
+        }
         KeyCode::Up => move_selection(app, -1),
         KeyCode::Down => move_selection(app, 1),
-        KeyCode::Left => {
-            if app.active_pane == ActivePane::Buckets {
-                cycle_region(app, -1);
-            }
+        KeyCode::Char(' ') if app.active_pane == ActivePane::Objects => {
+            app.toggle_mark();
+            move_selection(app, 1);
         }
-        KeyCode::Right => {
-            if app.active_pane == ActivePane::Buckets {
-                cycle_region(app, 1);
-            }
+        KeyCode::Char('a') | KeyCode::Char('A') if app.active_pane == ActivePane::Objects => {
+            app.mark_all();
+            app.push_status(&format!("{} objects marked", app.marked_keys.len()));
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') if app.active_pane == ActivePane::Objects => {
+            app.invert_marks();
+            app.push_status(&format!("{} objects marked", app.marked_keys.len()));
+        }
+        KeyCode::Left if app.active_pane == ActivePane::Buckets => {
+            cycle_region(app, -1);
+        }
+        KeyCode::Right if app.active_pane == ActivePane::Buckets => {
+            cycle_region(app, 1);
         }
         KeyCode::PageUp => move_selection(app, -5),
         KeyCode::PageDown => move_selection(app, 5),
         KeyCode::Home => jump_selection(app, true),
         KeyCode::End => jump_selection(app, false),
         KeyCode::Char('m') => {
+            app.editing_saved_mask = None;
             app.set_mode(AppMode::EditingMask);
             app.focus_mask_field(MaskEditorField::Pattern);
+            refresh_mask_preview(app);
             // Reset cursor position to end of pattern
             app.mask_draft.cursor_pos = app.mask_draft.pattern.len();
             app.push_status(
                 "Mask editor active – Type to enter pattern, Tab to switch fields, Enter to apply",
             );
         }
+        KeyCode::Char('M') => {
+            app.library_cursor = app
+                .library_cursor
+                .min(app.mask_library.masks.len().saturating_sub(1));
+            app.set_mode(AppMode::BrowsingMaskLibrary);
+        }
         KeyCode::Char('f') => {
             app.push_status("Refreshing buckets…");
             if let Err(err) = refresh_buckets(app, s3).await {
@@ -228,11 +323,39 @@ async fn handle_key_event(
                 app.push_status(&format!("Inspect failed: {err:#}"));
             }
         }
-        KeyCode::Enter => {
-            if app.active_pane == ActivePane::Buckets {
+        KeyCode::Enter if app.active_pane == ActivePane::Buckets => {
+            app.folder.clear();
+            app.clear_marks();
+            load_objects_for_selection(app, s3).await?;
+            // Automatically switch to Objects pane for intuitive navigation
+            app.active_pane = ActivePane::Objects;
+        }
+        KeyCode::Enter if app.active_pane == ActivePane::Objects => {
+            if let Some(folder) = app.selected_folder().map(|f| f.to_string()) {
+                open_folder(app, s3, folder, None).await;
+            }
+        }
+        KeyCode::Backspace if app.folder_view && app.active_pane == ActivePane::Objects => {
+            if let Some(parent) = app.parent_folder() {
+                let current = app.folder.clone();
+                open_folder(app, s3, parent, Some(current)).await;
+            }
+        }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.folder_view = !app.folder_view;
+            app.folder.clear();
+            app.push_status(if app.folder_view {
+                "Folder view: Enter opens a folder, Backspace goes up, S scopes the mask to it"
+            } else {
+                "Flat view: listing every key"
+            });
+            if app.selected_bucket_name().is_some() {
                 load_objects_for_selection(app, s3).await?;
-                // Automatically switch to Objects pane for intuitive navigation
-                app.active_pane = ActivePane::Objects;
+            }
+        }
+        KeyCode::Char('S') if app.folder_view => {
+            if let Err(err) = scope_mask_to_folder(app, s3).await {
+                app.push_status(&format!("Cannot scope to folder: {err:#}"));
             }
         }
         KeyCode::Char('s') => {
@@ -241,7 +364,7 @@ async fn handle_key_event(
             }
         }
         KeyCode::Char('r') => {
-            if let Err(err) = initiate_restore_flow(app) {
+            if let Err(err) = initiate_restore_flow(app, s3) {
                 app.push_status(&format!("Cannot request restore: {err:#}"));
             }
         }
@@ -255,6 +378,18 @@ async fn handle_key_event(
                 app.set_mode(AppMode::ViewingLog);
             }
         }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            app.dry_run = !app.dry_run;
+            app.push_status(if app.dry_run {
+                "Dry run on: confirmed actions write a plan file instead of calling S3"
+            } else {
+                "Dry run off: confirmed actions run against S3"
+            });
+        }
+        KeyCode::Char('j') | KeyCode::Char('J') => {
+            app.job_cursor = app.jobs.len().saturating_sub(1);
+            app.set_mode(AppMode::ViewingJobs);
+        }
         KeyCode::Char('t') | KeyCode::Char('T') => {
             if matches!(app.mode, AppMode::ViewingRestoreRequests) {
                 app.set_mode(AppMode::Browsing);
@@ -262,10 +397,14 @@ async fn handle_key_event(
                 app.set_mode(AppMode::ViewingRestoreRequests);
             }
         }
-        KeyCode::Esc => {
-            if app.active_mask.is_some() {
-                app.apply_mask(None);
-            }
+        // Esc drops the marks first, then the mask
+        KeyCode::Esc if !app.marked_keys.is_empty() => {
+            app.clear_marks();
+            app.push_status("Cleared marked objects");
+        }
+        KeyCode::Esc if app.active_mask.is_some() => {
+            app.apply_mask(None);
+            app.mask_draft.scope = None;
         }
         _ => {}
     }
@@ -273,86 +412,491 @@ async fn handle_key_event(
     Ok(false)
 }
 
-async fn handle_confirmation_keys(
-    key: KeyEvent,
-    app: &mut App,
-    s3: &S3Service,
-    tracker: &mut RestoreTracker,
-) -> Result<()> {
+fn handle_confirmation_keys(key: KeyEvent, app: &mut App, s3: &S3Service) -> Result<()> {
     match key.code {
         KeyCode::Esc | KeyCode::Char('n') => {
-            app.pending_action = None;
+            app.clear_pending_action();
             app.set_mode(AppMode::Browsing);
             app.push_status("Cancelled");
         }
         KeyCode::Enter | KeyCode::Char('y') => {
+            if !targets_ready(app) {
+                return Ok(());
+            }
+            app.set_mode(AppMode::Browsing);
             if let Some(action) = app.pending_action.take() {
                 match action {
                     PendingAction::Transition { target_class } => {
-                        execute_transition(app, s3, target_class).await?;
+                        execute_transition(app, s3, target_class)?;
                     }
-                    PendingAction::Restore { days } => {
-                        execute_restore(app, s3, tracker, days).await?;
+                    PendingAction::Restore { days, tier } => {
+                        execute_restore(app, s3, days, tier)?;
                     }
                 }
             }
-            app.set_mode(AppMode::Browsing);
+            app.clear_pending_action();
+        }
+        // Save the resolved targets for review instead of acting now
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            if !targets_ready(app) {
+                return Ok(());
+            }
+            match save_plan(app) {
+                Ok(()) => {
+                    app.clear_pending_action();
+                    app.set_mode(AppMode::Browsing);
+                }
+                Err(err) => app.push_status(&format!("Failed to save plan: {err:#}")),
+            }
+        }
+        // Restore options: ←→ picks the retrieval tier, ↑↓ or +/- the number of days
+        KeyCode::Left | KeyCode::Right => {
+            if let Some(PendingAction::Restore { tier, .. }) = app.pending_action.as_mut() {
+                *tier = tier.step(key.code == KeyCode::Right);
+            }
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::Char('+') | KeyCode::Char('-') => {
+            if let Some(PendingAction::Restore { days, .. }) = app.pending_action.as_mut() {
+                let delta = if matches!(key.code, KeyCode::Up | KeyCode::Char('+')) {
+                    1
+                } else {
+                    -1
+                };
+                *days = (*days + delta).clamp(1, MAX_RESTORE_DAYS);
+            }
         }
         _ => {}
     }
     Ok(())
 }
 
-fn handle_mask_editor_keys(key: KeyEvent, app: &mut App) {
+/// False (with a status message) while the bucket-wide target scan isn't usable yet
+fn targets_ready(app: &mut App) -> bool {
+    let Some(scan) = &app.target_scan else {
+        return true;
+    };
+    match &scan.status {
+        ScanStatus::Running => {
+            app.push_status("Still scanning the bucket – wait for the exact target count");
+            false
+        }
+        ScanStatus::Failed(_) => {
+            app.push_status("Bucket scan failed – press Esc and try again");
+            false
+        }
+        ScanStatus::Complete => true,
+    }
+}
+
+fn save_plan(app: &mut App) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before saving a plan")?
+        .to_string();
+    let action = app
+        .pending_action
+        .clone()
+        .context("No pending action to save")?;
+    let objects = match &action {
+        PendingAction::Transition { .. } => target_objects(app).into_owned(),
+        PendingAction::Restore { .. } => restore_candidates(app).objects,
+    };
+    if objects.is_empty() {
+        anyhow::bail!("no objects to put in the plan");
+    }
+    let mask = app.target_scan.as_ref().map(|scan| scan.mask.clone());
+    let count = objects.len();
+    let path = Plan::new(bucket, action, mask, &objects).save(&app.settings.plan_dir())?;
+    app.push_status(&format!(
+        "Saved plan for {} objects to {} – run `bucket-brigade apply-plan {}`",
+        count,
+        path.display(),
+        path.display()
+    ));
+    Ok(())
+}
+
+fn handle_progress_keys(key: KeyEvent, app: &mut App) {
+    let Some(job) = app.focused_job.and_then(|id| app.job(id)) else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
     match key.code {
+        // The job keeps running in the background once the popup is dismissed
+        KeyCode::Enter | KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Esc if job.is_running() => {
+            job.cancel();
+            let id = job.id;
+            app.push_status(&format!(
+                "Cancelling job #{id} after the objects in flight finish"
+            ));
+        }
         KeyCode::Esc => {
             app.set_mode(AppMode::Browsing);
-            app.push_status("Mask edit cancelled");
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => toggle_pause(app, job.id),
+        _ => {}
+    }
+}
+
+fn toggle_pause(app: &mut App, id: JobId) {
+    let Some(job) = app.job(id) else {
+        return;
+    };
+    if !job.is_running() || job.is_cancelling() {
+        return;
+    }
+    let message = if job.is_paused() {
+        job.resume();
+        format!("Resumed job #{id}")
+    } else {
+        job.pause();
+        format!("Pausing job #{id} after the objects in flight finish")
+    };
+    app.push_status(&message);
+}
+
+fn handle_jobs_popup_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('j') | KeyCode::Char('J') => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Up => {
+            app.job_cursor = app.job_cursor.saturating_sub(1);
+        }
+        KeyCode::Down if app.job_cursor + 1 < app.jobs.len() => {
+            app.job_cursor += 1;
         }
         KeyCode::Enter => {
-            if app.mask_draft.pattern.is_empty() {
-                app.push_status("Mask pattern cannot be empty");
-                return;
+            if let Some(job) = app.jobs.get(app.job_cursor) {
+                app.focused_job = Some(job.id);
+                app.set_mode(AppMode::ShowingProgress);
             }
-            // Generate a name based on the pattern and kind
-            let name = format!("{} '{}'", app.mask_draft.kind, app.mask_draft.pattern);
-            let mask = ObjectMask {
-                name,
-                pattern: app.mask_draft.pattern.clone(),
-                kind: app.mask_draft.kind.clone(),
-                case_sensitive: app.mask_draft.case_sensitive,
-                storage_class_filter: app.mask_draft.storage_class_filter.clone(),
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            let before = app.jobs.len();
+            app.jobs.retain(|job| job.is_running());
+            let cleared = before - app.jobs.len();
+            app.job_cursor = app.job_cursor.min(app.jobs.len().saturating_sub(1));
+            app.push_status(&format!("Cleared {} finished jobs", cleared));
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            if let Some(id) = app.jobs.get(app.job_cursor).map(|job| job.id) {
+                toggle_pause(app, id);
+            }
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            if let Some(job) = app.jobs.get(app.job_cursor)
+                && job.is_running()
+            {
+                job.cancel();
+                let id = job.id;
+                app.push_status(&format!(
+                    "Cancelling job #{id} after the objects in flight finish"
+                ));
+            }
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            // Re-run the objects a cancelled job never started as a new job
+            let Some(job) = app.jobs.get_mut(app.job_cursor) else {
+                return;
             };
+            if job.status != JobStatus::Cancelled || job.skipped.is_empty() {
+                app.push_status("Only cancelled jobs with remaining objects can be resumed");
+                return;
+            }
+            let remaining = std::mem::take(&mut job.skipped);
+            let (bucket, action) = (job.bucket.clone(), job.action.clone());
+            let journal = job.journal.take();
+            start_job(app, s3, bucket, action, remaining, journal);
+        }
+        _ => {}
+    }
+}
+
+fn handle_journal_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+            app.push_status(&format!(
+                "Kept {} interrupted job(s) for the next start",
+                app.pending_journals.len()
+            ));
+        }
+        KeyCode::Up => {
+            app.journal_cursor = app.journal_cursor.saturating_sub(1);
+        }
+        KeyCode::Down if app.journal_cursor + 1 < app.pending_journals.len() => {
+            app.journal_cursor += 1;
+        }
+        KeyCode::Enter if app.journal_cursor < app.pending_journals.len() => {
+            let journal = app.pending_journals.remove(app.journal_cursor);
+            let objects = journal.remaining_objects();
+            app.push_status(&format!(
+                "Resuming {} on {}: skipping {} keys already done",
+                journal.id,
+                journal.bucket,
+                journal.done_count()
+            ));
+            let (bucket, action) = (journal.bucket.clone(), journal.action.clone());
+            start_job(app, s3, bucket, action, objects, Some(journal));
+            // Stay on the list while there are more journals to decide on
+            if !app.pending_journals.is_empty() {
+                app.journal_cursor = app.journal_cursor.min(app.pending_journals.len() - 1);
+                app.set_mode(AppMode::ResumingJournals);
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D')
+            if app.journal_cursor < app.pending_journals.len() =>
+        {
+            let journal = app.pending_journals.remove(app.journal_cursor);
+            match journal.remove() {
+                Ok(()) => app.push_status(&format!("Discarded journal {}", journal.id)),
+                Err(err) => app.push_status(&format!(
+                    "Failed to discard journal {}: {err:#}",
+                    journal.id
+                )),
+            }
+            if app.pending_journals.is_empty() {
+                app.set_mode(AppMode::Browsing);
+            } else {
+                app.journal_cursor = app.journal_cursor.min(app.pending_journals.len() - 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+async fn handle_mask_library_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let count = app.mask_library.masks.len();
+    let selected = app.library_cursor;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('M') => app.set_mode(AppMode::Browsing),
+        KeyCode::Up => {
+            app.library_cursor = app.library_cursor.saturating_sub(1);
+        }
+        KeyCode::Down if selected + 1 < count => {
+            app.library_cursor += 1;
+        }
+        KeyCode::Enter if selected < count => {
+            let saved = &app.mask_library.masks[selected];
+            let bucket = app.selected_bucket_name().map(|b| b.to_string());
+            if !saved.usable_in(bucket.as_deref()) {
+                app.push_status(&format!(
+                    "'{}' is bound to bucket {}",
+                    saved.name,
+                    saved.bucket.as_deref().unwrap_or_default()
+                ));
+                return;
+            }
+            let mut mask = saved.mask.clone();
+            mask.name = saved.name.clone();
+            app.mask_draft = MaskDraft::from_mask(&mask);
             app.apply_mask(Some(mask));
             app.set_mode(AppMode::Browsing);
+            if let Some(bucket) = bucket {
+                refresh_object_tags(app, s3, &bucket).await;
+            }
         }
+        KeyCode::Char('e') | KeyCode::Char('E') if selected < count => {
+            app.mask_draft = MaskDraft::from_mask(&app.mask_library.masks[selected].mask);
+            app.editing_saved_mask = Some(selected);
+            app.set_mode(AppMode::EditingMask);
+            app.focus_mask_field(MaskEditorField::Pattern);
+            refresh_mask_preview(app);
+            app.push_status(&format!(
+                "Editing saved mask '{}' – Enter saves and applies it",
+                app.mask_library.masks[selected].name
+            ));
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            let Some(mask) = &app.active_mask else {
+                app.push_status("Apply a mask first, then save it to the library");
+                return;
+            };
+            app.library_form = LibraryForm {
+                name: mask.name.clone(),
+                ..LibraryForm::default()
+            };
+            app.set_mode(AppMode::NamingSavedMask);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') if selected < count => {
+            let saved = &app.mask_library.masks[selected];
+            app.library_form = LibraryForm {
+                editing: Some(selected),
+                name: saved.name.clone(),
+                description: saved.description.clone(),
+                bucket: saved.bucket.clone(),
+                field: LibraryFormField::Name,
+            };
+            app.set_mode(AppMode::NamingSavedMask);
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') if selected < count => {
+            match app.mask_library.duplicate(selected) {
+                Ok(index) => {
+                    app.library_cursor = index;
+                    app.push_status(&format!(
+                        "Duplicated as '{}'",
+                        app.mask_library.masks[index].name
+                    ));
+                }
+                Err(err) => app.push_status(&format!("Failed to save mask library: {err:#}")),
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') if selected < count => {
+            match app.mask_library.remove(selected) {
+                Ok(removed) => app.push_status(&format!("Deleted saved mask '{}'", removed.name)),
+                Err(err) => app.push_status(&format!("Failed to save mask library: {err:#}")),
+            }
+            app.library_cursor = selected.min(app.mask_library.masks.len().saturating_sub(1));
+        }
+        _ => {}
+    }
+}
+
+fn handle_library_form_keys(key: KeyEvent, app: &mut App) {
+    let selected_bucket = app.selected_bucket_name().map(|b| b.to_string());
+    let form = &mut app.library_form;
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::BrowsingMaskLibrary),
+        KeyCode::Tab | KeyCode::BackTab => form.field = form.field.next(),
+        KeyCode::Enter => {
+            if let Err(err) = save_library_form(app) {
+                app.push_status(&err);
+                return;
+            }
+            app.set_mode(AppMode::BrowsingMaskLibrary);
+        }
+        // Binding toggles between any bucket and the selected one
+        KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right
+            if form.field == LibraryFormField::Bucket =>
+        {
+            form.bucket = match form.bucket {
+                Some(_) => None,
+                None => selected_bucket,
+            };
+        }
+        KeyCode::Backspace => {
+            match form.field {
+                LibraryFormField::Name => form.name.pop(),
+                LibraryFormField::Description => form.description.pop(),
+                LibraryFormField::Bucket => None,
+            };
+        }
+        KeyCode::Char(ch) => match form.field {
+            LibraryFormField::Name => form.name.push(ch),
+            LibraryFormField::Description => form.description.push(ch),
+            LibraryFormField::Bucket => {}
+        },
+        _ => {}
+    }
+}
+
+/// Save the active mask under the form's name, or rename the entry being edited
+fn save_library_form(app: &mut App) -> Result<(), String> {
+    let form = app.library_form.clone();
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Saved masks need a name".to_string());
+    }
+    let taken = app
+        .mask_library
+        .masks
+        .iter()
+        .enumerate()
+        .any(|(index, saved)| saved.name == name && Some(index) != form.editing);
+
+    let result = match form.editing {
+        Some(index) => {
+            if taken {
+                return Err(format!("A saved mask is already called '{name}'"));
+            }
+            let saved = &mut app.mask_library.masks[index];
+            saved.name = name.clone();
+            saved.description = form.description.trim().to_string();
+            saved.bucket = form.bucket.clone();
+            app.mask_library.save().map(|()| index)
+        }
+        None => {
+            let mut mask = app
+                .active_mask
+                .clone()
+                .ok_or_else(|| "No active mask to save".to_string())?;
+            mask.name = name.clone();
+            app.mask_library.upsert(SavedMask {
+                name: name.clone(),
+                description: form.description.trim().to_string(),
+                bucket: form.bucket.clone(),
+                mask,
+            })
+        }
+    };
+    let index = result.map_err(|err| format!("Failed to save mask library: {err:#}"))?;
+    app.library_cursor = index;
+    app.push_status(&if taken && form.editing.is_none() {
+        format!("Replaced saved mask '{name}'")
+    } else {
+        format!("Saved mask '{name}'")
+    });
+    Ok(())
+}
+
+async fn handle_mask_editor_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let field = app.mask_field;
+    match key.code {
+        KeyCode::Esc => {
+            app.editing_saved_mask = None;
+            app.set_mode(AppMode::Browsing);
+            app.push_status("Mask edit cancelled");
+        }
+        KeyCode::Enter => match mask_from_draft(app) {
+            Ok(mut mask) => {
+                if let Some(index) = app.editing_saved_mask.take()
+                    && let Some(saved) = app.mask_library.masks.get_mut(index)
+                {
+                    mask.name = saved.name.clone();
+                    saved.mask = mask.clone();
+                    match app.mask_library.save() {
+                        Ok(()) => app.push_status(&format!("Updated saved mask '{}'", mask.name)),
+                        Err(err) => {
+                            app.push_status(&format!("Failed to save mask library: {err:#}"))
+                        }
+                    }
+                }
+                app.apply_mask(Some(mask));
+                app.set_mode(AppMode::Browsing);
+                if let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) {
+                    refresh_object_tags(app, s3, &bucket).await;
+                }
+            }
+            Err(err) => app.push_status(&err),
+        },
         KeyCode::Tab => {
             app.next_mask_field();
         }
         KeyCode::BackTab => {
             app.previous_mask_field();
         }
-        KeyCode::Backspace => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                if app.mask_draft.cursor_pos > 0 {
-                    app.mask_draft.pattern.remove(app.mask_draft.cursor_pos - 1);
-                    app.mask_draft.cursor_pos -= 1;
-                }
+        KeyCode::Backspace if field.is_text() && app.mask_draft.cursor_pos > 0 => {
+            let cursor = app.mask_draft.cursor_pos;
+            if let Some(text) = app.mask_draft.text_field_mut(field)
+                && let Some((prev, _)) = text[..cursor].char_indices().next_back()
+            {
+                text.remove(prev);
+                app.mask_draft.cursor_pos = prev;
             }
         }
-        KeyCode::Delete => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-                    app.mask_draft.pattern.remove(app.mask_draft.cursor_pos);
-                }
+        KeyCode::Delete if field.is_text() => {
+            let cursor = app.mask_draft.cursor_pos;
+            if let Some(text) = app.mask_draft.text_field_mut(field)
+                && cursor < text.len()
+            {
+                text.remove(cursor);
             }
         }
-        KeyCode::Left => match app.mask_field {
-            MaskEditorField::Pattern => {
-                if app.mask_draft.cursor_pos > 0 {
-                    app.mask_draft.cursor_pos -= 1;
-                }
-            }
+        KeyCode::Left => match field {
             MaskEditorField::Mode => app.cycle_mask_kind_backwards(),
             MaskEditorField::Case => app.toggle_mask_case(),
             MaskEditorField::StorageClass => {
@@ -364,13 +908,21 @@ fn handle_mask_editor_keys(key: KeyEvent, app: &mut App) {
                     .get(app.mask_draft.storage_class_cursor)
                     .and_then(|(_, filter)| filter.clone());
             }
-        },
-        KeyCode::Right => match app.mask_field {
-            MaskEditorField::Pattern => {
-                if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-                    app.mask_draft.cursor_pos += 1;
+            MaskEditorField::Pattern
+            | MaskEditorField::Size
+            | MaskEditorField::Modified
+            | MaskEditorField::Tag => {
+                let cursor = app.mask_draft.cursor_pos;
+                if let Some((prev, _)) = app
+                    .mask_draft
+                    .text_field(field)
+                    .and_then(|text| text[..cursor].char_indices().next_back())
+                {
+                    app.mask_draft.cursor_pos = prev;
                 }
             }
+        },
+        KeyCode::Right => match field {
             MaskEditorField::Mode => app.cycle_mask_kind(),
             MaskEditorField::Case => app.toggle_mask_case(),
             MaskEditorField::StorageClass => {
@@ -382,18 +934,29 @@ fn handle_mask_editor_keys(key: KeyEvent, app: &mut App) {
                     .get(app.mask_draft.storage_class_cursor)
                     .and_then(|(_, filter)| filter.clone());
             }
-        },
-        KeyCode::Home => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.cursor_pos = 0;
+            MaskEditorField::Pattern
+            | MaskEditorField::Size
+            | MaskEditorField::Modified
+            | MaskEditorField::Tag => {
+                let cursor = app.mask_draft.cursor_pos;
+                if let Some(ch) = app
+                    .mask_draft
+                    .text_field(field)
+                    .and_then(|text| text[cursor..].chars().next())
+                {
+                    app.mask_draft.cursor_pos += ch.len_utf8();
+                }
             }
+        },
+        KeyCode::Home if field.is_text() => {
+            app.mask_draft.cursor_pos = 0;
         }
         KeyCode::End => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.cursor_pos = app.mask_draft.pattern.len();
+            if let Some(text) = app.mask_draft.text_field(field) {
+                app.mask_draft.cursor_pos = text.len();
             }
         }
-        KeyCode::Char(' ') => match app.mask_field {
+        KeyCode::Char(' ') if !field.is_text() => match field {
             MaskEditorField::Mode => app.cycle_mask_kind(),
             MaskEditorField::Case => app.toggle_mask_case(),
             MaskEditorField::StorageClass => {
@@ -404,37 +967,97 @@ fn handle_mask_editor_keys(key: KeyEvent, app: &mut App) {
                     .get(app.mask_draft.storage_class_cursor)
                     .and_then(|(_, filter)| filter.clone());
             }
-            MaskEditorField::Pattern => {
-                app.mask_draft
-                    .pattern
-                    .insert(app.mask_draft.cursor_pos, ' ');
-                app.mask_draft.cursor_pos += 1;
-            }
+            _ => {}
         },
         KeyCode::Char(ch) => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.pattern.insert(app.mask_draft.cursor_pos, ch);
-                app.mask_draft.cursor_pos += 1;
+            let cursor = app.mask_draft.cursor_pos;
+            if let Some(text) = app.mask_draft.text_field_mut(field) {
+                text.insert(cursor, ch);
+                app.mask_draft.cursor_pos += ch.len_utf8();
             }
         }
         _ => {}
     }
 }
 
-fn handle_storage_class_selector(key: KeyEvent, app: &mut App) {
+/// Build the mask described by the editor, or explain what's wrong with it
+fn mask_from_draft(app: &App) -> Result<ObjectMask, String> {
+    let draft = &app.mask_draft;
+    let size_filter = match draft.size_input.trim() {
+        "" => None,
+        input => Some(
+            input
+                .parse::<SizeFilter>()
+                .map_err(|err| format!("Size filter: {err}"))?,
+        ),
+    };
+    let modified_filter = match draft.modified_input.trim() {
+        "" => None,
+        input => Some(
+            input
+                .parse::<DateFilter>()
+                .map_err(|err| format!("Modified filter: {err}"))?,
+        ),
+    };
+    let tag_filter = match draft.tag_input.trim() {
+        "" => None,
+        input => Some(
+            input
+                .parse::<TagFilter>()
+                .map_err(|err| format!("Tag filter: {err}"))?,
+        ),
+    };
+    if draft.pattern.is_empty()
+        && size_filter.is_none()
+        && modified_filter.is_none()
+        && tag_filter.is_none()
+        && draft.scope.is_none()
+    {
+        return Err("Mask pattern cannot be empty".to_string());
+    }
+    // Generate a name based on the pattern and kind
+    let name = format!("{} '{}'", draft.kind, draft.pattern);
+    let mut mask = ObjectMask::new(
+        name,
+        draft.pattern.clone(),
+        pattern_kind(&draft.kind, &draft.pattern),
+        draft.case_sensitive,
+    )
+    .map_err(|err| err.to_string())?;
+    mask.storage_class_filter = draft.storage_class_filter.clone();
+    mask.size_filter = size_filter;
+    mask.modified_filter = modified_filter;
+    mask.tag_filter = tag_filter;
+    mask.scope = draft.scope.clone();
+    Ok(mask)
+}
+
+/// Re-run the editor's match preview against the draft as it stands
+fn refresh_mask_preview(app: &mut App) {
+    let mask = mask_from_draft(app);
+    app.preview_mask(mask);
+}
+
+/// An empty pattern only narrows by the other filters, which an empty prefix does
+/// for every kind
+fn pattern_kind(kind: &MaskKind, pattern: &str) -> MaskKind {
+    if pattern.is_empty() {
+        MaskKind::Prefix
+    } else {
+        kind.clone()
+    }
+}
+
+fn handle_storage_class_selector(key: KeyEvent, app: &mut App, s3: &S3Service) {
     match key.code {
         KeyCode::Esc => {
             app.set_mode(AppMode::Browsing);
         }
-        KeyCode::Up => {
-            if app.storage_class_cursor > 0 {
-                app.storage_class_cursor -= 1;
-            }
+        KeyCode::Up if app.storage_class_cursor > 0 => {
+            app.storage_class_cursor -= 1;
         }
-        KeyCode::Down => {
-            if app.storage_class_cursor + 1 < StorageClassTier::selectable().len() {
-                app.storage_class_cursor += 1;
-            }
+        KeyCode::Down if app.storage_class_cursor + 1 < StorageClassTier::selectable().len() => {
+            app.storage_class_cursor += 1;
         }
         KeyCode::Enter => {
             if let Some(selected) = StorageClassTier::selectable().get(app.storage_class_cursor) {
@@ -453,6 +1076,7 @@ fn handle_storage_class_selector(key: KeyEvent, app: &mut App) {
                         app.pending_action = Some(PendingAction::Transition {
                             target_class: selected.clone(),
                         });
+                        resolve_targets(app, s3);
                         app.set_mode(AppMode::Confirming);
                         app.push_status(&format!(
                             "Confirm transition to {} (press Enter to confirm)",
@@ -473,7 +1097,7 @@ fn begin_storage_selection(app: &mut App, intent: StorageIntent) -> Result<()> {
                 anyhow::bail!("Select a bucket first");
             }
             if target_count(app) == 0 {
-                anyhow::bail!("Select at least one object (mask or row)");
+                anyhow::bail!("Select at least one object (mask, marked rows or row)");
             }
         }
     }
@@ -483,11 +1107,25 @@ fn begin_storage_selection(app: &mut App, intent: StorageIntent) -> Result<()> {
     Ok(())
 }
 
-fn initiate_restore_flow(app: &mut App) -> Result<()> {
+fn initiate_restore_flow(app: &mut App, s3: &S3Service) -> Result<()> {
     if app.selected_bucket_name().is_none() || target_count(app) == 0 {
         anyhow::bail!("Select objects to restore first");
     }
 
+    // Restore state of unloaded pages is unknown, so let the full scan decide
+    if app.active_mask.is_some()
+        && app.marked_keys.is_empty()
+        && (app.folder_view || app.has_more_objects())
+    {
+        app.pending_action = Some(PendingAction::Restore {
+            days: DEFAULT_RESTORE_DAYS,
+            tier: RetrievalTier::default(),
+        });
+        resolve_targets(app, s3);
+        app.set_mode(AppMode::Confirming);
+        return Ok(());
+    }
+
     let need_restore = app.count_objects_needing_restore();
     let already_restoring = app.count_objects_restoring();
 
@@ -503,7 +1141,11 @@ fn initiate_restore_flow(app: &mut App) -> Result<()> {
         return Ok(());
     }
 
-    app.pending_action = Some(PendingAction::Restore { days: 7 });
+    app.pending_action = Some(PendingAction::Restore {
+        days: DEFAULT_RESTORE_DAYS,
+        tier: RetrievalTier::default(),
+    });
+    resolve_targets(app, s3);
     app.set_mode(AppMode::Confirming);
 
     if already_restoring > 0 {
@@ -520,125 +1162,80 @@ fn initiate_restore_flow(app: &mut App) -> Result<()> {
     Ok(())
 }
 
-async fn execute_transition(
-    app: &mut App,
-    s3: &S3Service,
-    target_class: StorageClassTier,
-) -> Result<()> {
+fn execute_transition(app: &mut App, s3: &S3Service, target_class: StorageClassTier) -> Result<()> {
     let bucket = app
         .selected_bucket_name()
         .context("Select a bucket before transitioning")?
         .to_string();
-    let keys = target_keys(app);
-    if keys.is_empty() {
+    let objects = target_objects(app).into_owned();
+    if objects.is_empty() {
         app.push_status("No objects selected for transition");
         return Ok(());
     }
 
-    // Initialize progress tracking
-    let total = keys.len();
-    app.progress = Some(crate::app::ProgressState::new(
-        format!("Transitioning to {}", target_class.label()),
-        total,
-    ));
-    app.set_mode(AppMode::ShowingProgress);
-
-    let mut success_count = 0;
-    let mut error_count = 0;
-
-    for (index, key) in keys.iter().enumerate() {
-        // Update progress
-        if let Some(progress) = &mut app.progress {
-            progress.update(index + 1, Some(key.clone()));
-        }
-
-        // Yield to allow UI updates
-        tokio::task::yield_now().await;
-
-        match s3
-            .transition_storage_class(&bucket, key, target_class.clone())
-            .await
-        {
-            Ok(_) => {
-                success_count += 1;
-            }
-            Err(err) => {
-                error_count += 1;
-                app.push_status(&format!("Transition failed for {key}: {err:#}"));
-            }
-        }
-    }
-
-    // Clear progress and return to browsing
-    app.progress = None;
-    app.set_mode(AppMode::Browsing);
-
-    // Show summary
-    if error_count > 0 {
-        app.push_status(&format!(
-            "Transition complete: {} succeeded, {} failed",
-            success_count, error_count
-        ));
-    } else {
-        app.push_status(&format!(
-            "Successfully transitioned {} objects to {}",
-            success_count,
-            target_class.label()
-        ));
-    }
-
-    load_objects_for_selection(app, s3).await?;
+    dispatch_action(
+        app,
+        s3,
+        bucket,
+        PendingAction::Transition { target_class },
+        objects,
+    );
     Ok(())
 }
 
-async fn execute_restore(
-    app: &mut App,
-    s3: &S3Service,
-    tracker: &mut RestoreTracker,
-    days: i32,
-) -> Result<()> {
-    let bucket = app
-        .selected_bucket_name()
-        .context("Select a bucket before restoring")?
-        .to_string();
+struct RestoreCandidates {
+    objects: Vec<ObjectInfo>,
+    already_restoring: usize,
+    already_available: usize,
+}
 
-    // Get objects and filter to only those needing restore
-    let all_keys = target_keys(app);
-    let objects_map: std::collections::HashMap<_, _> = if app.active_mask.is_some() {
-        app.filtered_objects
-            .iter()
-            .map(|o| (o.key.clone(), o))
-            .collect()
-    } else {
-        app.objects.iter().map(|o| (o.key.clone(), o)).collect()
+/// Targets that still need a restore, with counts of the ones that don't
+fn restore_candidates(app: &App) -> RestoreCandidates {
+    let loaded_states = loaded_restore_states(app);
+    let mut candidates = RestoreCandidates {
+        objects: Vec::new(),
+        already_restoring: 0,
+        already_available: 0,
     };
 
-    let mut keys_to_restore = Vec::new();
-    let mut already_restoring = 0;
-    let mut already_available = 0;
-
-    for key in &all_keys {
-        if let Some(obj) = objects_map.get(key) {
-            match &obj.restore_state {
-                Some(crate::models::RestoreState::InProgress { .. }) => {
-                    already_restoring += 1;
-                }
-                Some(crate::models::RestoreState::Available) => {
-                    already_available += 1;
-                }
-                _ => {
-                    // Only restore if it's a Glacier object that needs restore
-                    if matches!(
-                        obj.storage_class,
-                        crate::models::StorageClassTier::GlacierFlexibleRetrieval
-                            | crate::models::StorageClassTier::GlacierDeepArchive
-                    ) {
-                        keys_to_restore.push(key.clone());
-                    }
+    for obj in target_objects(app).iter() {
+        let state = obj
+            .restore_state
+            .as_ref()
+            .or_else(|| loaded_states.get(obj.key.as_str()).copied());
+        match state {
+            Some(RestoreState::InProgress { .. }) => {
+                candidates.already_restoring += 1;
+            }
+            Some(RestoreState::Available) => {
+                candidates.already_available += 1;
+            }
+            _ => {
+                // Only restore if it's a Glacier object that needs restore
+                if matches!(
+                    obj.storage_class,
+                    StorageClassTier::GlacierFlexibleRetrieval
+                        | StorageClassTier::GlacierDeepArchive
+                ) {
+                    candidates.objects.push(obj.clone());
                 }
             }
         }
     }
+    candidates
+}
+
+fn execute_restore(app: &mut App, s3: &S3Service, days: i32, tier: RetrievalTier) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before restoring")?
+        .to_string();
+
+    let RestoreCandidates {
+        objects: objects_to_restore,
+        already_restoring,
+        already_available,
+    } = restore_candidates(app);
 
     if already_restoring > 0 {
         app.push_status(&format!(
@@ -653,79 +1250,240 @@ async fn execute_restore(
         ));
     }
 
-    if keys_to_restore.is_empty() {
+    if objects_to_restore.is_empty() {
         app.push_status("No objects need restore");
         return Ok(());
     }
 
-    // Initialize progress tracking
-    let total = keys_to_restore.len();
-    app.progress = Some(crate::app::ProgressState::new(
-        "Requesting Glacier restore".to_string(),
-        total,
+    dispatch_action(
+        app,
+        s3,
+        bucket,
+        PendingAction::Restore { days, tier },
+        objects_to_restore,
+    );
+    Ok(())
+}
+
+/// Run a confirmed action, or only write its plan when dry-run mode is on
+fn dispatch_action(
+    app: &mut App,
+    s3: &S3Service,
+    bucket: String,
+    action: PendingAction,
+    objects: Vec<ObjectInfo>,
+) {
+    if !app.dry_run {
+        start_job(app, s3, bucket, action, objects, None);
+        return;
+    }
+    match dry_run::write_plan(&bucket, &action, &objects) {
+        Ok(report) => {
+            app.push_status(&format!(
+                "Dry run: {} requests ({}) written to {}",
+                report.requests,
+                format_size(report.total_bytes).trim_start(),
+                report.path.display()
+            ));
+            app.push_status(&format!(
+                "Dry run by source class – {}",
+                report.class_breakdown()
+            ));
+        }
+        Err(err) => app.push_status(&format!("Failed to write dry-run plan: {err:#}")),
+    }
+}
+
+/// Spawn a background job and show its progress popup.
+/// New jobs get a fresh journal; resumed jobs pass in the one they came from.
+fn start_job(
+    app: &mut App,
+    s3: &S3Service,
+    bucket: String,
+    action: PendingAction,
+    objects: Vec<ObjectInfo>,
+    journal: Option<Journal>,
+) {
+    let journal = match journal {
+        Some(journal) => Some(journal),
+        None => match Journal::create(&bucket, &action, &objects) {
+            Ok(journal) => Some(journal),
+            Err(err) => {
+                app.push_status(&format!(
+                    "Could not create job journal, progress will not be resumable: {err:#}"
+                ));
+                None
+            }
+        },
+    };
+    let id = app.next_job_id;
+    app.next_job_id += 1;
+    let job = Job::spawn(id, s3, bucket, action, objects, &app.settings, journal);
+    app.push_status(&format!(
+        "Started job #{}: {} ({} objects)",
+        id, job.progress.operation, job.progress.total
     ));
+    app.jobs.push(job);
+    app.focused_job = Some(id);
     app.set_mode(AppMode::ShowingProgress);
+}
 
+/// Apply progress reported by background jobs
+async fn poll_jobs(app: &mut App, s3: &S3Service, tracker: &mut RestoreTracker) {
+    let mut messages = Vec::new();
     let mut restored_keys = Vec::new();
-    let mut success_count = 0;
-    let mut error_count = 0;
-
-    for (index, key) in keys_to_restore.iter().enumerate() {
-        // Update progress
-        if let Some(progress) = &mut app.progress {
-            progress.update(index + 1, Some(key.clone()));
+    let mut finished = Vec::new();
+
+    for job in app.jobs.iter_mut().filter(|job| job.is_running()) {
+        let mut tracked_keys = Vec::new();
+        for outcome in job.poll() {
+            match (&job.action, outcome.failure) {
+                (PendingAction::Transition { .. }, Some(failure)) => {
+                    messages.push(format!(
+                        "Transition failed for {}: {}",
+                        outcome.key, failure.message
+                    ));
+                }
+                (PendingAction::Restore { .. }, Some(failure)) => {
+                    messages.push(format!(
+                        "✗ Restore failed for {}: {}",
+                        outcome.key, failure.message
+                    ));
+                }
+                (PendingAction::Restore { .. }, None) => {
+                    tracked_keys.push(outcome.key.clone());
+                    restored_keys.push((job.bucket.clone(), outcome.key));
+                }
+                (PendingAction::Transition { .. }, None) => {}
+            }
+        }
+        // Track this poll's restore requests with a single save
+        if let PendingAction::Restore { days, tier } = &job.action {
+            tracker.add_requests(&job.bucket, tracked_keys, *days, *tier);
         }
+        if !job.is_running() {
+            let is_transition = matches!(job.action, PendingAction::Transition { .. });
+            let mut summary = job.summary();
+            if job.status == JobStatus::Cancelled {
+                summary.push_str(" – press j, then r to run the rest");
+            }
+            finished.push((job.id, job.bucket.clone(), is_transition, summary));
+        }
+    }
 
-        // Yield to allow UI updates
-        tokio::task::yield_now().await;
+    for message in messages {
+        app.push_status(&message);
+    }
 
-        match s3.request_restore(&bucket, key, days).await {
-            Ok(_) => {
-                success_count += 1;
-                // Track the restore request
-                tracker.add_request(bucket.clone(), key.clone(), days);
-                restored_keys.push(key.clone());
+    // AWS doesn't immediately reflect the restore status change, so we update it in memory
+    if !restored_keys.is_empty() {
+        let selected_bucket = app.selected_bucket_name().map(|b| b.to_string());
+        for (bucket, key) in restored_keys {
+            if selected_bucket.as_deref() != Some(bucket.as_str()) {
+                continue;
             }
-            Err(err) => {
-                error_count += 1;
-                let detail = describe_restore_error(&err);
-                app.push_status(&format!("✗ Restore failed for {key}: {detail}"));
+            if let Some(obj) = app.objects.iter_mut().find(|o| o.key == key) {
+                obj.restore_state = Some(RestoreState::InProgress { expiry: None });
             }
         }
+        app.refresh_filtered();
     }
 
-    // Clear progress and return to browsing
-    app.progress = None;
-    app.set_mode(AppMode::Browsing);
-
-    // Show summary
-    if error_count > 0 {
-        app.push_status(&format!(
-            "Restore requests complete: {} succeeded, {} failed",
-            success_count, error_count
-        ));
-    } else {
-        app.push_status(&format!(
-            "Successfully requested restore for {} objects",
-            success_count
-        ));
+    for (id, bucket, is_transition, summary) in finished {
+        app.push_status(&format!("Job #{id}: {summary}"));
+        if app.focused_job == Some(id) && app.mode == AppMode::ShowingProgress {
+            app.set_mode(AppMode::Browsing);
+        }
+        // Storage classes changed, so reload the listing if the user is still looking at it
+        if is_transition
+            && app.selected_bucket_name() == Some(bucket.as_str())
+            && let Err(err) = load_objects_for_selection(app, s3).await
+        {
+            app.push_status(&format!("Failed to reload objects: {err:#}"));
+        }
     }
+}
 
-    // Manually update restore status for successfully restored objects
-    // AWS doesn't immediately reflect the status change, so we update it in memory
-    for obj in app.objects.iter_mut() {
-        if restored_keys.contains(&obj.key) {
-            obj.restore_state = Some(crate::models::RestoreState::InProgress { expiry: None });
+/// Periodically HEAD in-progress tracked restores and apply the results
+async fn poll_restore_tracker(app: &mut App, s3: &S3Service, tracker: &mut RestoreTracker) {
+    if let Some(poll) = app.restore_poll.as_mut() {
+        let Some(results) = poll.take_results().await else {
+            return;
+        };
+        app.restore_poll = None;
+
+        let changes = tracker.apply_poll(results);
+        notify_completed_restores(app, &changes);
+        let selected_bucket = app.selected_bucket_name().map(|b| b.to_string());
+        for change in &changes {
+            match (&change.previous, &change.current) {
+                (RestoreState::InProgress { .. }, RestoreState::Available) => {
+                    app.push_status(&format!(
+                        "Restore complete: {}/{}{}",
+                        change.bucket,
+                        change.key,
+                        change
+                            .expires_at
+                            .as_deref()
+                            .map(|expiry| format!(" (available until {expiry})"))
+                            .unwrap_or_default()
+                    ));
+                }
+                (_, RestoreState::Expired) => {
+                    app.push_status(&format!(
+                        "Restored copy expired: {}/{}",
+                        change.bucket, change.key
+                    ));
+                }
+                _ => {}
+            }
+            if selected_bucket.as_deref() == Some(change.bucket.as_str())
+                && let Some(obj) = app.objects.iter_mut().find(|o| o.key == change.key)
+            {
+                obj.restore_state = Some(change.current.clone());
+            }
+        }
+        if !changes.is_empty() {
+            app.refresh_filtered();
         }
+        return;
     }
 
-    // Update filtered objects if a mask is active
-    if app.active_mask.is_some() {
-        let mask = app.active_mask.clone();
-        app.apply_mask(mask);
+    let due = app
+        .last_restore_poll
+        .is_none_or(|last| last.elapsed() >= app.settings.restore_poll_interval());
+    if due {
+        app.last_restore_poll = Some(std::time::Instant::now());
+        app.restore_poll = Some(RestorePoll::start(s3, &tracker.get_active_requests()));
     }
+}
 
-    Ok(())
+/// Alert the user about restores that went from in progress to available
+fn notify_completed_restores(app: &mut App, changes: &[StatusChange]) {
+    let completed: Vec<StatusChange> = changes
+        .iter()
+        .filter(|change| {
+            matches!(change.previous, RestoreState::InProgress { .. })
+                && change.current == RestoreState::Available
+        })
+        .cloned()
+        .collect();
+    let Some(first) = completed.first() else {
+        return;
+    };
+
+    let message = if completed.len() == 1 {
+        format!("Restore complete: {}/{}", first.bucket, first.key)
+    } else {
+        format!("{} Glacier restores complete", completed.len())
+    };
+    notify::terminal(&message);
+
+    if let Some(command) = app.settings.notify_command.as_deref()
+        && let Err(err) = notify::run_command(command, &completed)
+    {
+        app.push_status(&format!("Failed to run notify_command: {err:#}"));
+    }
 }
 
 async fn refresh_buckets(app: &mut App, s3: &S3Service) -> Result<()> {
@@ -747,22 +1505,7 @@ async fn refresh_selected_object(app: &mut App, s3: &S3Service) -> Result<()> {
     if let Some(existing) = app.objects.iter_mut().find(|o| o.key == key) {
         *existing = refreshed.clone();
     }
-    if let Some(mask) = &app.active_mask {
-        app.filtered_objects = app
-            .objects
-            .iter()
-            .filter(|&obj| {
-                let key_matches = mask.matches(&obj.key);
-                let storage_matches = mask
-                    .storage_class_filter
-                    .as_ref()
-                    .map(|filter| &obj.storage_class == filter)
-                    .unwrap_or(true);
-                key_matches && storage_matches
-            })
-            .cloned()
-            .collect();
-    }
+    app.refresh_filtered();
     app.push_status("Object metadata refreshed");
     Ok(())
 }
@@ -771,23 +1514,32 @@ async fn load_objects_for_selection(app: &mut App, s3: &S3Service) -> Result<()>
     if let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) {
         app.reset_pagination();
         app.is_loading_objects = true;
-        app.push_status(&format!("Loading objects from {}...", bucket));
+        if app.folder_view {
+            app.push_status(&format!("Loading folder s3://{}/{}...", bucket, app.folder));
+        } else {
+            // Prefix masks are pushed down so only the matching subtree is listed
+            app.listing_prefix = app.desired_listing_prefix();
+            match &app.listing_prefix {
+                Some(prefix) => app.push_status(&format!(
+                    "Loading objects from {} under prefix '{}'...",
+                    bucket, prefix
+                )),
+                None => app.push_status(&format!("Loading objects from {}...", bucket)),
+            }
+        }
 
         // Skip full count for now - it can take forever on large buckets
         // We'll show loaded count vs "more available" instead
         app.total_object_count = None;
 
         // Load first page
-        const PAGE_SIZE: i32 = 200;
-        match s3
-            .list_objects_paginated(&bucket, None, None, PAGE_SIZE)
-            .await
-        {
+        match fetch_listing_page(app, s3, &bucket, None).await {
             Ok((mut objects, next_token)) => {
                 objects.sort_by(|a, b| a.key.cmp(&b.key));
                 app.set_objects(objects);
                 app.continuation_token = next_token;
-                app.apply_mask(app.active_mask.clone());
+                app.retain_listed_marks();
+                app.refresh_filtered();
 
                 let loaded = app.objects.len();
                 if app.has_more_objects() {
@@ -798,6 +1550,7 @@ async fn load_objects_for_selection(app: &mut App, s3: &S3Service) -> Result<()>
 
                 // Fetch restore status for Glacier objects
                 refresh_glacier_restore_status(app, s3, &bucket).await;
+                refresh_object_tags(app, s3, &bucket).await;
             }
             Err(err) => {
                 app.push_status(&format!("Failed to load objects: {err:#}"));
@@ -817,11 +1570,8 @@ async fn load_more_objects(app: &mut App, s3: &S3Service) -> Result<()> {
     if let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) {
         app.is_loading_objects = true;
 
-        const PAGE_SIZE: i32 = 200;
-        match s3
-            .list_objects_paginated(&bucket, None, app.continuation_token.clone(), PAGE_SIZE)
-            .await
-        {
+        let token = app.continuation_token.clone();
+        match fetch_listing_page(app, s3, &bucket, token).await {
             Ok((mut new_objects, next_token)) => {
                 new_objects.sort_by(|a, b| a.key.cmp(&b.key));
                 app.append_objects(new_objects);
@@ -836,6 +1586,7 @@ async fn load_more_objects(app: &mut App, s3: &S3Service) -> Result<()> {
 
                 // Fetch restore status for newly loaded Glacier objects
                 refresh_glacier_restore_status(app, s3, &bucket).await;
+                refresh_object_tags(app, s3, &bucket).await;
             }
             Err(err) => {
                 app.push_status(&format!("Failed to load more: {err:#}"));
@@ -847,6 +1598,66 @@ async fn load_more_objects(app: &mut App, s3: &S3Service) -> Result<()> {
     Ok(())
 }
 
+/// Load one page of the current listing: the subtree under `listing_prefix`, or in
+/// folder view only the current folder, whose subfolders are added to `app.folders`
+async fn fetch_listing_page(
+    app: &mut App,
+    s3: &S3Service,
+    bucket: &str,
+    token: Option<String>,
+) -> Result<(Vec<ObjectInfo>, Option<String>)> {
+    const PAGE_SIZE: i32 = 200;
+    if !app.folder_view {
+        return s3
+            .list_objects_paginated(bucket, app.listing_prefix.as_deref(), token, PAGE_SIZE)
+            .await;
+    }
+    let (folders, objects, next_token) = s3
+        .list_folder_paginated(bucket, &app.folder, token, PAGE_SIZE)
+        .await?;
+    app.folders.extend(folders);
+    Ok((objects, next_token))
+}
+
+/// Switch to `folder` in folder view and list it, keeping the cursor on `select` if it's a subfolder
+async fn open_folder(app: &mut App, s3: &S3Service, folder: String, select: Option<String>) {
+    app.folder = folder;
+    if let Err(err) = load_objects_for_selection(app, s3).await {
+        app.push_status(&format!("Failed to load folder: {err:#}"));
+        return;
+    }
+    if let Some(index) = select.and_then(|select| app.folders.iter().position(|f| *f == select)) {
+        app.selected_object = index;
+    }
+}
+
+/// Limit the active mask (or a new match-everything mask) to the current folder,
+/// recursively, and list every key under it
+async fn scope_mask_to_folder(app: &mut App, s3: &S3Service) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+    let scope = Some(app.folder.clone()).filter(|folder| !folder.is_empty());
+    let mut mask = match app.active_mask.clone() {
+        Some(mask) => mask,
+        None => {
+            let name = scope.clone().unwrap_or_else(|| bucket.clone());
+            ObjectMask::new(name, String::new(), MaskKind::Prefix, true)?
+        }
+    };
+    mask.scope = scope;
+    app.mask_draft.scope = mask.scope.clone();
+    app.folder_view = false;
+    app.apply_mask(Some(mask));
+    load_objects_for_selection(app, s3).await?;
+    app.push_status(&format!(
+        "Mask scoped to s3://{}/{} and everything below it; actions target every match there",
+        bucket, app.folder
+    ));
+    Ok(())
+}
+
 /// Fetch accurate restore status for Glacier/Deep Archive objects
 async fn refresh_glacier_restore_status(app: &mut App, s3: &S3Service, bucket: &str) {
     use crate::models::StorageClassTier;
@@ -872,17 +1683,51 @@ async fn refresh_glacier_restore_status(app: &mut App, s3: &S3Service, bucket: &
     let status_results = s3.batch_refresh_restore_status(bucket, &glacier_keys).await;
 
     // Update objects with fetched restore status
-    for (key, restore_state) in status_results {
+    for (key, status) in status_results {
         if let Some(obj) = app.objects.iter_mut().find(|o| o.key == key) {
-            obj.restore_state = restore_state;
+            obj.restore_state = status.and_then(|status| status.state);
         }
     }
 
-    // Re-apply mask if active to update filtered list
-    if app.active_mask.is_some() {
-        let mask = app.active_mask.clone();
-        app.apply_mask(mask);
+    // Re-filter in place so a page load keeps the cursor and the marks
+    app.refresh_filtered();
+}
+
+/// Fetch tags for loaded objects when the active mask filters on them
+async fn refresh_object_tags(app: &mut App, s3: &S3Service, bucket: &str) {
+    let Some(mask) = app.active_mask.clone() else {
+        return;
+    };
+    if !mask.needs_tags() {
+        return;
+    }
+    let untagged: Vec<ObjectInfo> = app
+        .objects
+        .iter()
+        .filter(|obj| obj.tags.is_none() && mask.matches_without_tags(obj))
+        .cloned()
+        .collect();
+    if untagged.is_empty() {
+        return;
+    }
+
+    let mut failed = 0;
+    for (key, tags) in s3.batch_fetch_tags(bucket, &untagged).await {
+        if tags.is_none() {
+            failed += 1;
+        }
+        if let Some(obj) = app.objects.iter_mut().find(|o| o.key == key) {
+            obj.tags = tags;
+        }
     }
+    if failed > 0 {
+        app.push_status(&format!(
+            "Could not read tags for {} objects; they are excluded from the mask",
+            failed
+        ));
+    }
+    // A lazy load shouldn't move the cursor or reset marks the way applying a mask does
+    app.refresh_filtered();
 }
 
 fn move_selection(app: &mut App, delta: isize) {
@@ -907,7 +1752,7 @@ fn move_selection(app: &mut App, delta: isize) {
             }
         }
         ActivePane::Objects => {
-            let len = app.active_objects().len();
+            let len = app.object_rows();
             if len == 0 {
                 return;
             }
@@ -927,24 +1772,16 @@ fn move_selection(app: &mut App, delta: isize) {
 
 fn jump_selection(app: &mut App, start: bool) {
     match app.active_pane {
-        ActivePane::Buckets => {
-            if !app.buckets.is_empty() {
-                let new_idx = if start { 0 } else { app.buckets.len() - 1 };
-                if new_idx != app.selected_bucket {
-                    app.selected_bucket = new_idx;
-                    app.last_bucket_change = Some(std::time::Instant::now());
-                    app.pending_bucket_load = true;
-                }
+        ActivePane::Buckets if !app.buckets.is_empty() => {
+            let new_idx = if start { 0 } else { app.buckets.len() - 1 };
+            if new_idx != app.selected_bucket {
+                app.selected_bucket = new_idx;
+                app.last_bucket_change = Some(std::time::Instant::now());
+                app.pending_bucket_load = true;
             }
         }
-        ActivePane::Objects => {
-            if !app.active_objects().is_empty() {
-                app.selected_object = if start {
-                    0
-                } else {
-                    app.active_objects().len() - 1
-                };
-            }
+        ActivePane::Objects if app.object_rows() > 0 => {
+            app.selected_object = if start { 0 } else { app.object_rows() - 1 };
         }
         _ => {}
     }
@@ -974,24 +1811,99 @@ fn cycle_region(app: &mut App, delta: isize) {
 }
 
 fn target_count(app: &App) -> usize {
-    if app.active_mask.is_some() {
-        app.filtered_objects.len()
-    } else if app.selected_object < app.objects.len() {
-        1
+    if let Some(scan) = &app.target_scan {
+        scan.targets.len()
     } else {
-        0
+        app.action_objects().len()
+    }
+}
+
+fn target_objects(app: &App) -> Cow<'_, [ObjectInfo]> {
+    match (&app.target_scan, &app.pending_targets) {
+        (Some(scan), _) => Cow::Borrowed(&scan.targets),
+        (None, Some(targets)) => Cow::Borrowed(targets),
+        (None, None) => app.action_objects(),
     }
 }
 
-fn target_keys(app: &App) -> Vec<String> {
-    if app.active_mask.is_some() {
-        app.filtered_objects.iter().map(|o| o.key.clone()).collect()
+fn target_count_label(app: &App) -> String {
+    match &app.target_scan {
+        Some(scan) if !scan.is_complete() => "scanning…".to_string(),
+        _ => format!("{}", target_count(app)),
+    }
+}
+
+/// Restore states of loaded objects; listings never include them, so scanned targets fall back here
+fn loaded_restore_states(app: &App) -> HashMap<&str, &RestoreState> {
+    app.objects
+        .iter()
+        .filter_map(|obj| {
+            obj.restore_state
+                .as_ref()
+                .map(|state| (obj.key.as_str(), state))
+        })
+        .collect()
+}
+
+fn needs_restore(obj: &ObjectInfo, loaded: &HashMap<&str, &RestoreState>) -> bool {
+    let state = obj
+        .restore_state
+        .as_ref()
+        .or_else(|| loaded.get(obj.key.as_str()).copied());
+    matches!(
+        obj.storage_class,
+        StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
+    ) && !matches!(
+        state,
+        Some(RestoreState::Available) | Some(RestoreState::InProgress { .. })
+    )
+}
+
+/// Resolve the pending action's targets across the whole bucket when a mask is active
+/// and no rows are marked; otherwise snapshot the marked rows or the cursor row
+fn resolve_targets(app: &mut App, s3: &S3Service) {
+    app.target_scan = None;
+    app.pending_targets = None;
+    let mask = match app.active_mask.clone() {
+        Some(mask) if app.marked_keys.is_empty() => mask,
+        _ => {
+            app.pending_targets = Some(app.action_objects().into_owned());
+            return;
+        }
+    };
+    let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) else {
+        return;
+    };
+
+    // Loaded pages only cover the mask when they came from a matching, exhausted listing
+    // of the whole subtree; folder view leaves out everything below the current folder
+    if app.folder_view || app.has_more_objects() || app.listing_is_stale() {
+        app.target_scan = Some(FullScan::start(s3, bucket, mask));
+        app.push_status("Scanning entire bucket for mask matches…");
     } else {
-        app.objects
-            .get(app.selected_object)
-            .map(|o| vec![o.key.clone()])
-            .unwrap_or_default()
+        // Every object is already loaded, so the local matches are the full set
+        app.target_scan = Some(FullScan::from_loaded(bucket, mask, &app.objects));
+    }
+}
+
+fn poll_target_scan(app: &mut App) {
+    let Some(scan) = app.target_scan.as_mut() else {
+        return;
+    };
+    if !scan.poll() {
+        return;
     }
+    let message = match &scan.status {
+        ScanStatus::Complete => format!(
+            "Bucket scan complete: {} of {} objects match ({})",
+            scan.targets.len(),
+            scan.listed,
+            format_size(scan.total_bytes).trim_start()
+        ),
+        ScanStatus::Failed(err) => format!("Bucket scan failed: {err}"),
+        ScanStatus::Running => return,
+    };
+    app.push_status(&message);
 }
 
 fn draw(frame: &mut ratatui::Frame, app: &App, tracker: &RestoreTracker) {
@@ -1034,6 +1946,13 @@ fn draw(frame: &mut ratatui::Frame, app: &App, tracker: &RestoreTracker) {
         AppMode::ViewingLog => draw_log_popup(frame, app),
         AppMode::ViewingRestoreRequests => draw_tracked_requests_popup(frame, tracker),
         AppMode::ShowingProgress => draw_progress_popup(frame, app),
+        AppMode::ViewingJobs => draw_jobs_popup(frame, app),
+        AppMode::ResumingJournals => draw_journals_popup(frame, app),
+        AppMode::BrowsingMaskLibrary => draw_mask_library_popup(frame, app),
+        AppMode::NamingSavedMask => {
+            draw_mask_library_popup(frame, app);
+            draw_library_form_popup(frame, app);
+        }
         AppMode::Browsing => {}
     }
 }
@@ -1093,12 +2012,19 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         ""
     };
 
+    let listing_scope = app
+        .listing_prefix
+        .as_ref()
+        .map(|prefix| format!(" under '{}'", prefix))
+        .unwrap_or_default();
+
     let title = if let Some(mask) = &app.active_mask {
         format!(
-            "Objects – mask: {} ({} matches of {} loaded{}){}",
+            "Objects – mask: {} ({} matches of {} loaded{}{}){}",
             mask.summary(),
             app.filtered_objects.len(),
             loaded_count,
+            listing_scope,
             if loaded_count < total_count {
                 format!(" of {}", total_count)
             } else {
@@ -1112,6 +2038,21 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
             loaded_count, total_count, loading_indicator
         )
     };
+    let title = if app.marked_keys.is_empty() {
+        title
+    } else {
+        format!("{} – {} marked", title, app.marked_keys.len())
+    };
+    let title = if app.folder_view {
+        format!(
+            "{} ({} folders) │ {}",
+            folder_breadcrumb(app),
+            app.folders.len(),
+            title
+        )
+    } else {
+        title
+    };
     let title_style = Style::default()
         .fg(Color::LightCyan)
         .add_modifier(Modifier::BOLD);
@@ -1122,15 +2063,33 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         .style(Style::default().bg(Color::Black));
 
     // Calculate available width for the key column
-    // 2 (marker) + 1 (space) + 13 (size) + 1 (space) + 20 (storage) + 1 (space) + 13 (restore) + 2 (borders) = 53
-    let fixed_width = 53;
+    // 2 (marker) + 2 (mark) + 1 (space) + 13 (size) + 1 (space) + 20 (storage) + 1 (space) + 13 (restore) + 2 (borders) = 55
+    let fixed_width = 55;
     let key_width = area.width.saturating_sub(fixed_width).max(20) as usize;
 
-    let items: Vec<ListItem> = objects
-        .iter()
-        .enumerate()
-        .map(|(idx, obj)| {
-            let is_selected = idx == app.selected_object;
+    let folder_rows = app.folder_rows();
+    let folder_items = folder_rows.iter().enumerate().map(|(idx, folder)| {
+        let is_selected = idx == app.selected_object;
+        let name = folder.strip_prefix(app.folder.as_str()).unwrap_or(folder);
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                if is_selected { "► " } else { "  " },
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("▸ {name}"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]))
+    });
+    let items: Vec<ListItem> = folder_items
+        .chain(objects.iter().enumerate().map(|(idx, obj)| {
+            let is_selected = idx + folder_rows.len() == app.selected_object;
             let marker = if is_selected { "►" } else { " " };
             let marker_style = if is_selected {
                 Style::default()
@@ -1139,19 +2098,31 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
             } else {
                 Style::default().fg(Color::DarkGray)
             };
+            let is_marked = app.is_marked(obj);
             let key_style = if is_selected {
                 Style::default()
                     .fg(Color::LightGreen)
                     .add_modifier(Modifier::BOLD)
+            } else if is_marked {
+                Style::default().fg(Color::LightMagenta)
             } else {
                 Style::default().fg(Color::White)
             };
 
+            // Keys are shown relative to the open folder in folder view
+            let key = if app.folder_view {
+                obj.key
+                    .strip_prefix(app.folder.as_str())
+                    .unwrap_or(&obj.key)
+            } else {
+                obj.key.as_str()
+            };
+
             // Truncate or pad the key to fixed width
-            let key_display = if obj.key.len() > key_width {
-                format!("{}…", &obj.key[..key_width.saturating_sub(1)])
+            let key_display = if key.len() > key_width {
+                format!("{}…", &key[..key_width.saturating_sub(1)])
             } else {
-                format!("{:<width$}", obj.key, width = key_width)
+                format!("{:<width$}", key, width = key_width)
             };
 
             // Format storage class with fixed width
@@ -1193,6 +2164,12 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
 
             let spans = vec![
                 Span::styled(marker.to_string(), marker_style),
+                Span::styled(
+                    if is_marked { "● " } else { "  " },
+                    Style::default()
+                        .fg(Color::LightMagenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
                 Span::raw(" "),
                 Span::styled(key_display, key_style),
                 Span::raw(" "),
@@ -1203,11 +2180,11 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
             ];
 
             ListItem::new(Line::from(spans))
-        })
+        }))
         .collect();
     let mut state = ListState::default();
-    if !objects.is_empty() {
-        state.select(Some(app.selected_object.min(objects.len() - 1)));
+    if !items.is_empty() {
+        state.select(Some(app.selected_object.min(items.len() - 1)));
     }
     let list = List::new(items)
         .highlight_style(Style::default().bg(Color::Blue))
@@ -1215,6 +2192,13 @@ fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
+/// "bucket › logs › 2024" for the folder open in folder view
+fn folder_breadcrumb(app: &App) -> String {
+    let mut crumbs = vec![app.selected_bucket_name().unwrap_or("(no bucket)")];
+    crumbs.extend(app.folder.split_terminator('/'));
+    crumbs.join(" › ")
+}
+
 fn draw_object_detail(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let title_style = Style::default()
         .fg(Color::LightYellow)
@@ -1255,6 +2239,13 @@ fn draw_object_detail(frame: &mut ratatui::Frame, area: Rect, app: &App) {
             Line::from(format!("Last modified: {}", modified)),
             Line::from(format!("Restore: {}", restore)),
         ]
+    } else if let Some(folder) = app.selected_folder() {
+        vec![
+            Line::from(format!("Folder: {}", folder)),
+            Line::from(
+                "Enter opens it; S scopes the mask to the open folder and everything below it",
+            ),
+        ]
     } else {
         vec![Line::from("No object selected")]
     };
@@ -1309,10 +2300,19 @@ fn draw_status(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         .rev()
         .map(|msg| Line::from(msg.clone()))
         .collect();
+    let running = app.running_job_count();
+    let mut title = if running > 0 {
+        format!("Status – {} job(s) running, press j", running)
+    } else {
+        "Status".to_string()
+    };
+    if app.dry_run {
+        title.push_str(" – DRY RUN (d to turn off)");
+    }
     let block = Block::default()
         .borders(Borders::ALL)
         .title(Span::styled(
-            "Status",
+            title,
             Style::default()
                 .fg(Color::LightCyan)
                 .add_modifier(Modifier::BOLD),
@@ -1332,6 +2332,8 @@ fn draw_command_bar(frame: &mut ratatui::Frame, area: Rect) {
         Span::raw(" "),
         Span::styled(" m ", key_style),
         Span::raw("ask "),
+        Span::styled(" b ", key_style),
+        Span::raw("rowse "),
         Span::styled(" s ", key_style),
         Span::raw("torage "),
         Span::styled(" r ", key_style),
@@ -1342,6 +2344,10 @@ fn draw_command_bar(frame: &mut ratatui::Frame, area: Rect) {
         Span::raw("refresh "),
         Span::styled(" t ", key_style),
         Span::raw("racker "),
+        Span::styled(" j ", key_style),
+        Span::raw("obs "),
+        Span::styled(" d ", key_style),
+        Span::raw("ry-run "),
         Span::styled(" ? ", key_style),
         Span::raw("help "),
         Span::styled(" l ", key_style),
@@ -1357,7 +2363,7 @@ fn draw_command_bar(frame: &mut ratatui::Frame, area: Rect) {
 }
 
 fn draw_mask_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(70, 40, frame.size());
+    let area = centered_rect(70, 85, frame.size());
     draw_modal_surface(frame, area);
 
     let title_style = Style::default()
@@ -1378,50 +2384,54 @@ fn draw_mask_popup(frame: &mut ratatui::Frame, app: &App) {
     let inactive_style = Style::default().fg(Color::Gray);
     let hint_style = Style::default().fg(Color::DarkGray);
 
-    // Create pattern field with cursor
-    let is_pattern_focused = matches!(app.mask_field, MaskEditorField::Pattern);
-    let mut pattern_spans = vec![Span::styled("Pattern: ", label_style)];
-
-    if is_pattern_focused {
-        // Show cursor in pattern field
-        let before_cursor = &app.mask_draft.pattern[..app.mask_draft.cursor_pos];
-        let cursor_char = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-            app.mask_draft
-                .pattern
+    let text_field = |label: &'static str, field: MaskEditorField, empty: &'static str| {
+        let value = app.mask_draft.text_field(field).map_or("", String::as_str);
+        let mut spans = vec![Span::styled(label, label_style)];
+        if app.mask_field == field {
+            // Show cursor in the focused field
+            let cursor = app.mask_draft.cursor_pos.min(value.len());
+            let before_cursor = &value[..cursor];
+            let cursor_char = value[cursor..]
                 .chars()
-                .nth(app.mask_draft.cursor_pos)
-                .unwrap()
-                .to_string()
-        } else {
-            " ".to_string()
-        };
-        let after_cursor = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-            &app.mask_draft.pattern[app.mask_draft.cursor_pos + 1..]
-        } else {
-            ""
-        };
-
-        pattern_spans.push(Span::styled(before_cursor, active_style));
-        pattern_spans.push(Span::styled(
-            cursor_char,
-            Style::default().fg(Color::Black).bg(Color::LightYellow),
-        ));
-        pattern_spans.push(Span::styled(after_cursor, active_style));
-    } else {
-        let display = if app.mask_draft.pattern.is_empty() {
-            "(empty)"
+                .next()
+                .map_or_else(|| " ".to_string(), |ch| ch.to_string());
+            let after_cursor = value[cursor..]
+                .char_indices()
+                .nth(1)
+                .map_or("", |(idx, _)| &value[cursor + idx..]);
+            spans.push(Span::styled(before_cursor, active_style));
+            spans.push(Span::styled(
+                cursor_char,
+                Style::default().fg(Color::Black).bg(Color::LightYellow),
+            ));
+            spans.push(Span::styled(after_cursor, active_style));
+        } else if value.is_empty() {
+            spans.push(Span::styled(empty, inactive_style));
         } else {
-            &app.mask_draft.pattern
-        };
-        pattern_spans.push(Span::styled(display, inactive_style));
-    }
+            spans.push(Span::styled(value, inactive_style));
+        }
+        spans
+    };
+    let pattern_spans = text_field("Pattern: ", MaskEditorField::Pattern, "(empty)");
+    // Compile as you type so a bad regex/glob/expression is caught before Enter
+    let pattern_hint = match ObjectMask::validate(
+        &pattern_kind(&app.mask_draft.kind, &app.mask_draft.pattern),
+        &app.mask_draft.pattern,
+        app.mask_draft.case_sensitive,
+    ) {
+        Ok(()) => Span::styled("↑ Type your filter pattern here", hint_style),
+        Err(err) => Span::styled(
+            format!("✗ {err}"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+    };
 
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(pattern_spans),
         Line::from(vec![
             Span::styled("          ", Style::default()),
-            Span::styled("↑ Type your filter pattern here", hint_style),
+            pattern_hint,
         ]),
         Line::from(""),
         Line::from(vec![
@@ -1492,6 +2502,38 @@ fn draw_mask_popup(frame: &mut ratatui::Frame, app: &App) {
             Span::styled("  (use ←/→ or space)", hint_style),
         ]),
         Line::from(""),
+        Line::from(text_field("Size: ", MaskEditorField::Size, "Any")),
+        Line::from(vec![
+            Span::styled("      ", Style::default()),
+            Span::styled("e.g. > 1 GiB, < 128 KiB", hint_style),
+        ]),
+        Line::from(text_field("Modified: ", MaskEditorField::Modified, "Any")),
+        Line::from(vec![
+            Span::styled("          ", Style::default()),
+            Span::styled(
+                "e.g. > 90d (older), < 7d (newer), 2023-01-01..2023-06-30",
+                hint_style,
+            ),
+        ]),
+        Line::from(text_field("Tag: ", MaskEditorField::Tag, "Any")),
+        Line::from(vec![
+            Span::styled("     ", Style::default()),
+            Span::styled(
+                "e.g. retention=short, project exists, project missing",
+                hint_style,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Folder: ", label_style),
+            Span::styled(
+                app.mask_draft
+                    .scope
+                    .as_ref()
+                    .map_or_else(|| "Whole bucket".to_string(), |scope| format!("{scope}**")),
+                inactive_style,
+            ),
+            Span::styled("  (S in folder view scopes to a folder)", hint_style),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled(
@@ -1516,11 +2558,63 @@ fn draw_mask_popup(frame: &mut ratatui::Frame, app: &App) {
             ),
             Span::styled(" cancel", hint_style),
         ]),
+        Line::from(""),
     ];
+    text.extend(mask_preview_lines(app, label_style, hint_style));
     let para = Paragraph::new(text).block(block);
     frame.render_widget(para, area);
 }
 
+/// Live match count and first matching keys for the mask being edited
+fn mask_preview_lines(app: &App, label_style: Style, hint_style: Style) -> Vec<Line<'static>> {
+    let preview = &app.mask_preview;
+    if let Some(err) = &preview.error {
+        return vec![Line::from(vec![
+            Span::styled("Preview: ", label_style),
+            Span::styled(err.clone(), Style::default().fg(Color::Red)),
+        ])];
+    }
+
+    let loaded = app.objects.len();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Preview: ", label_style),
+        Span::styled(
+            format!("{} of {} loaded objects match", preview.matches, loaded),
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    for key in &preview.keys {
+        lines.push(Line::from(Span::styled(
+            format!("  {key}"),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    if preview.matches > preview.keys.len() {
+        lines.push(Line::from(Span::styled(
+            format!("  … and {} more", preview.matches - preview.keys.len()),
+            hint_style,
+        )));
+    }
+    if preview.untagged > 0 {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} more pass the other filters; their tags are fetched when the mask is applied",
+                preview.untagged
+            ),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if app.has_more_objects() {
+        lines.push(Line::from(Span::styled(
+            "Partial: only loaded objects are previewed; actions on the mask scan the whole bucket",
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    lines
+}
+
 fn draw_storage_popup(frame: &mut ratatui::Frame, app: &App) {
     let area = centered_rect(40, 50, frame.size());
     draw_modal_surface(frame, area);
@@ -1541,7 +2635,7 @@ fn draw_storage_popup(frame: &mut ratatui::Frame, app: &App) {
 }
 
 fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(60, 40, frame.size());
+    let area = centered_rect(60, 70, frame.size());
     draw_modal_surface(frame, area);
 
     let key_style = Style::default()
@@ -1567,14 +2661,14 @@ fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App) {
                 lines.push(Line::from(""));
                 lines.push(Line::from(vec![
                     Span::raw("  Objects: "),
-                    Span::styled(format!("{}", target_count(app)), highlight_style),
+                    Span::styled(target_count_label(app), highlight_style),
                 ]));
                 lines.push(Line::from(vec![
                     Span::raw("  Target:  "),
                     Span::styled(target_class.label(), highlight_style),
                 ]));
             }
-            PendingAction::Restore { days } => {
+            PendingAction::Restore { days, tier } => {
                 lines.push(Line::from(vec![Span::styled(
                     "Request Glacier Restore",
                     warn_style,
@@ -1582,20 +2676,138 @@ fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App) {
                 lines.push(Line::from(""));
                 lines.push(Line::from(vec![
                     Span::raw("  Objects:  "),
-                    Span::styled(format!("{}", target_count(app)), highlight_style),
+                    Span::styled(target_count_label(app), highlight_style),
                 ]));
+                if app
+                    .target_scan
+                    .as_ref()
+                    .is_some_and(|scan| scan.is_complete())
+                {
+                    let loaded_states = loaded_restore_states(app);
+                    let need_restore = target_objects(app)
+                        .iter()
+                        .filter(|obj| needs_restore(obj, &loaded_states))
+                        .count();
+                    lines.push(Line::from(vec![
+                        Span::raw("  Need restore: "),
+                        Span::styled(format!("{}", need_restore), highlight_style),
+                    ]));
+                }
                 lines.push(Line::from(vec![
                     Span::raw("  Duration: "),
-                    Span::styled(format!("{} days", days), highlight_style),
+                    Span::styled(format!("◂ {} days ▸", days), highlight_style),
+                    Span::raw("  (↑↓ or +/-)"),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  Tier:     "),
+                    Span::styled(format!("◂ {} ▸", tier.label()), highlight_style),
+                    Span::raw("  (←→)"),
+                ]));
+                let targets = target_objects(app);
+                let has_flexible = targets
+                    .iter()
+                    .any(|obj| obj.storage_class == StorageClassTier::GlacierFlexibleRetrieval);
+                let has_deep = targets
+                    .iter()
+                    .any(|obj| obj.storage_class == StorageClassTier::GlacierDeepArchive);
+                if has_flexible {
+                    let estimate = tier
+                        .typical_completion(&StorageClassTier::GlacierFlexibleRetrieval)
+                        .unwrap_or("not available");
+                    lines.push(Line::from(format!(
+                        "  Expected: {estimate} for Glacier Flexible Retrieval"
+                    )));
+                }
+                if has_deep {
+                    match tier.typical_completion(&StorageClassTier::GlacierDeepArchive) {
+                        Some(estimate) => lines.push(Line::from(format!(
+                            "  Expected: {estimate} for Deep Archive"
+                        ))),
+                        None => lines.push(Line::from(vec![Span::styled(
+                            format!(
+                                "  {} is not offered for Deep Archive; those objects will fail",
+                                tier.label()
+                            ),
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        )])),
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(scan) = &app.target_scan {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("  Mask:       "),
+            Span::styled(scan.mask.summary(), highlight_style),
+        ]));
+        match &scan.status {
+            ScanStatus::Running => {
+                lines.push(Line::from(vec![Span::styled(
+                    format!(
+                        "  Scanning bucket… {} pages, {} listed, {} matching so far",
+                        scan.pages,
+                        scan.listed,
+                        scan.targets.len()
+                    ),
+                    warn_style,
+                )]));
+            }
+            ScanStatus::Complete => {
+                lines.push(Line::from(vec![
+                    Span::raw("  Scope:      "),
+                    Span::styled(
+                        format!(
+                            "all of {}/{} ({} objects listed)",
+                            scan.bucket,
+                            scan.mask.server_prefix().unwrap_or(""),
+                            scan.listed
+                        ),
+                        highlight_style,
+                    ),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  Total size: "),
+                    Span::styled(
+                        format_size(scan.total_bytes).trim_start().to_string(),
+                        highlight_style,
+                    ),
                 ]));
             }
+            ScanStatus::Failed(err) => {
+                lines.push(Line::from(vec![Span::styled(
+                    format!("  Bucket scan failed: {err}"),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )]));
+            }
         }
     }
 
+    let targets_final = app
+        .target_scan
+        .as_ref()
+        .is_none_or(|scan| scan.is_complete());
+    if let Some(action) = &app.pending_action
+        && targets_final
+    {
+        lines.extend(cost_lines(app, action, highlight_style));
+    }
+
+    if app.dry_run {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "  DRY RUN – a plan file is written instead of calling S3",
+            warn_style,
+        )]));
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
         Span::styled(" Enter ", key_style),
         Span::raw(" Confirm   "),
+        Span::styled(" w ", key_style),
+        Span::raw(" Save plan   "),
         Span::styled(" Esc ", key_style),
         Span::raw(" Cancel"),
     ]));
@@ -1614,6 +2826,95 @@ fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App) {
     frame.render_widget(para, area);
 }
 
+/// Cost estimate section of the confirm popup, from the local pricing table
+fn cost_lines(app: &App, action: &PendingAction, value_style: Style) -> Vec<Line<'static>> {
+    let region = app
+        .buckets
+        .get(app.selected_bucket)
+        .and_then(|bucket| bucket.region.as_deref());
+    let now = chrono::Utc::now();
+    let estimate = match action {
+        PendingAction::Transition { .. } => {
+            app.pricing
+                .estimate(region, action, target_objects(app).iter(), now)
+        }
+        PendingAction::Restore { .. } => {
+            let loaded_states = loaded_restore_states(app);
+            let targets = target_objects(app);
+            let candidates = targets
+                .iter()
+                .filter(|obj| needs_restore(obj, &loaded_states));
+            app.pricing.estimate(region, action, candidates, now)
+        }
+    };
+    let Some(estimate) = estimate else {
+        return Vec::new();
+    };
+    let cost = |amount: f64| format_cost(amount, &estimate.currency);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(format!("  Estimated cost ({}):", estimate.region)),
+    ];
+    match action {
+        PendingAction::Transition { .. } => {
+            lines.push(Line::from(vec![
+                Span::raw("    Storage:        "),
+                Span::styled(
+                    format!("{}/month", cost(estimate.monthly_storage_delta)),
+                    value_style,
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    Requests:       "),
+                Span::styled(cost(estimate.requests), value_style),
+            ]));
+            if estimate.retrieval > 0.0 {
+                lines.push(Line::from(vec![
+                    Span::raw("    Retrieval:      "),
+                    Span::styled(cost(estimate.retrieval), value_style),
+                ]));
+            }
+            if estimate.early_deletion_objects > 0 {
+                lines.push(Line::from(vec![
+                    Span::raw("    Early deletion: "),
+                    Span::styled(cost(estimate.early_deletion), value_style),
+                    Span::raw(format!(
+                        "  ({} objects under the minimum storage duration)",
+                        estimate.early_deletion_objects
+                    )),
+                ]));
+            }
+        }
+        PendingAction::Restore { days, .. } => {
+            lines.push(Line::from(vec![
+                Span::raw("    Retrieval:      "),
+                Span::styled(cost(estimate.retrieval), value_style),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    Requests:       "),
+                Span::styled(cost(estimate.requests), value_style),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    Restored copy:  "),
+                Span::styled(cost(estimate.restored_copy), value_style),
+                Span::raw(format!("  (Standard storage for {days} days)")),
+            ]));
+        }
+    }
+    lines.push(Line::from(vec![
+        Span::raw("    One-off total:  "),
+        Span::styled(cost(estimate.one_off_total()), value_style),
+    ]));
+    if estimate.unpriced > 0 {
+        lines.push(Line::from(format!(
+            "    {} objects have no price in the table and are not included",
+            estimate.unpriced
+        )));
+    }
+    lines
+}
+
 fn draw_help_popup(frame: &mut ratatui::Frame) {
     let area = centered_rect(80, 80, frame.size());
     draw_modal_surface(frame, area);
@@ -1656,15 +2957,62 @@ fn draw_help_popup(frame: &mut ratatui::Frame) {
             Span::raw(" - Load bucket objects (Buckets pane)"),
         ]),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "MARKING OBJECTS (Objects pane)",
+            header_style,
+        )]),
+        Line::from(vec![
+            Span::styled("Space", key_style),
+            Span::raw(" - Mark/unmark row  "),
+            Span::styled("Shift+↑↓", key_style),
+            Span::raw(" - Extend range  "),
+            Span::styled("a", key_style),
+            Span::raw(" - Mark all  "),
+            Span::styled("v", key_style),
+            Span::raw(" - Invert"),
+        ]),
+        Line::from(
+            "   • Marked rows take precedence over the mask and cursor row; Esc clears them",
+        ),
+        Line::from(""),
+        Line::from(vec![Span::styled("FOLDER VIEW", header_style)]),
+        Line::from(vec![
+            Span::styled("b", key_style),
+            Span::raw(" - Toggle folder view (list one folder at a time, subfolders first)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::raw(" - Open folder  "),
+            Span::styled("Backspace", key_style),
+            Span::raw(" - Parent folder  "),
+            Span::styled("S", key_style),
+            Span::raw(" - Scope mask/actions to this folder recursively"),
+        ]),
+        Line::from(""),
         Line::from(vec![Span::styled("OBJECT FILTERING (MASKS)", header_style)]),
         Line::from(vec![
             Span::styled("m", key_style),
             Span::raw(" - Open mask editor to create/edit filters"),
         ]),
-        Line::from("   • Tab moves between fields: Name → Pattern → Mode → Case"),
-        Line::from("   • Match modes: Prefix, Suffix, Contains, Regex (use arrows/space to cycle)"),
+        Line::from(
+            "   • Tab moves between fields: Pattern → Mode → Case → Storage class → Size → Modified → Tag",
+        ),
+        Line::from(
+            "   • Match modes: Prefix, Suffix, Contains, Regex, Glob, Expression (arrows/space cycle)",
+        ),
+        Line::from("   • Glob: * and ? stay within a folder, ** crosses folders, [abc] classes"),
+        Line::from("   • Expression: prefix:logs/ & !suffix:.gz, with AND/OR/NOT and parentheses"),
+        Line::from(
+            "   • Size: > 1 GiB or < 128 KiB  Modified: > 90d, < 7d or 2023-01-01..2023-06-30",
+        ),
+        Line::from("   • Tag: retention=short, project exists or project missing"),
+        Line::from("   • A live preview shows the match count and first matching loaded keys"),
         Line::from("   • Enter applies the mask, Esc cancels"),
         Line::from("   • Active masks filter the object list and target all matching objects"),
+        Line::from(vec![
+            Span::styled("M", key_style),
+            Span::raw(" - Mask library: load, edit, save, rename, duplicate or delete named masks"),
+        ]),
         Line::from(vec![
             Span::styled("Esc", key_style),
             Span::raw(" - Clear active mask and show all objects"),
@@ -1675,17 +3023,39 @@ fn draw_help_popup(frame: &mut ratatui::Frame) {
             Span::styled("s", key_style),
             Span::raw(" - Transition objects to a different storage class"),
         ]),
+        Line::from("   • With marked rows: transitions exactly the marked objects"),
         Line::from("   • Without mask: transitions the selected object only"),
         Line::from("   • With mask: transitions ALL matching objects"),
-        Line::from("   • Press 'o' during confirmation to toggle restore-before-transition"),
+        Line::from("   • Press 'w' during confirmation to save the targets as a plan file instead"),
         Line::from(vec![
             Span::styled("r", key_style),
-            Span::raw(" - Request 7-day Glacier restore for selected/masked objects"),
+            Span::raw(
+                " - Request Glacier restore for marked/selected/masked objects (pick tier and days)",
+            ),
         ]),
+        Line::from("   • In the confirm popup: ←→ picks the retrieval tier, ↑↓ or +/- the days"),
         Line::from(vec![
             Span::styled("i", key_style),
             Span::raw(" - Inspect selected object (refreshes metadata via HeadObject)"),
         ]),
+        Line::from(vec![
+            Span::styled("j", key_style),
+            Span::raw(" - Background jobs (confirmed operations keep running while you browse)"),
+        ]),
+        Line::from(vec![
+            Span::styled("d", key_style),
+            Span::raw(
+                " - Toggle dry run (confirmed actions write a JSON lines plan instead of calling S3)",
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("p", key_style),
+            Span::raw(" / "),
+            Span::styled("Esc", key_style),
+            Span::raw(
+                " - In a job's progress popup: pause/resume, or cancel after objects in flight",
+            ),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled("OTHER COMMANDS", header_style)]),
         Line::from(vec![
@@ -1733,7 +3103,7 @@ fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTrac
     draw_modal_surface(frame, area);
 
     let block = Block::default()
-        .title("Tracked Restore Requests – Esc/t/Enter to close")
+        .title("Tracked Restore Requests – x clear available/expired, Esc/t/Enter to close")
         .borders(Borders::ALL)
         .style(Style::default().bg(Color::Black));
 
@@ -1749,8 +3119,15 @@ fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTrac
             Span::styled("Status", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(" | "),
             Span::styled("Days", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" | "),
+            Span::styled("Tier", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" | "),
+            Span::styled(
+                "Completed / Expires",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
         ]),
-        Line::from(std::iter::repeat('-').take(100).collect::<String>()),
+        Line::from(std::iter::repeat_n('-', 100).collect::<String>()),
     ];
 
     if requests.is_empty() {
@@ -1784,7 +3161,16 @@ fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTrac
                 Span::raw(format!("{} | ", req.bucket)),
                 Span::raw(format!("{} | ", req.key)),
                 Span::styled(format!("{} | ", status_text), status_style),
-                Span::raw(format!("{} days", req.days)),
+                Span::raw(format!("{} days | ", req.days)),
+                Span::raw(format!(
+                    "{} | ",
+                    req.tier.map(|tier| tier.label()).unwrap_or("-")
+                )),
+                Span::raw(format!(
+                    "{} / {}",
+                    format_timestamp(req.completed_at.as_deref()),
+                    format_timestamp(req.expires_at.as_deref())
+                )),
             ]));
         }
     }
@@ -1793,14 +3179,26 @@ fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTrac
     frame.render_widget(para, area);
 }
 
+/// Short local rendering of an RFC 3339 timestamp, or "-" when unknown
+fn format_timestamp(value: Option<&str>) -> String {
+    value
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
 fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
     let area = centered_rect(70, 30, frame.size());
     draw_modal_surface(frame, area);
 
-    let progress = match &app.progress {
-        Some(p) => p,
-        None => return,
+    let Some(job) = app.focused_job.and_then(|id| app.job(id)) else {
+        return;
     };
+    let progress = &job.progress;
 
     let title_style = Style::default()
         .fg(Color::LightCyan)
@@ -1808,7 +3206,7 @@ fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
 
     let block = Block::default()
         .title(Span::styled(
-            format!(" {} ", progress.operation),
+            format!(" Job #{}: {} ", job.id, progress.operation),
             title_style,
         ))
         .borders(Borders::ALL)
@@ -1825,7 +3223,9 @@ fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
             Constraint::Length(3), // Progress bar
             Constraint::Length(2), // Counter
             Constraint::Length(2), // Current item
+            Constraint::Length(2), // Retry and failure breakdown
             Constraint::Min(1),    // Padding
+            Constraint::Length(1), // Key hints
         ])
         .split(inner);
 
@@ -1841,14 +3241,40 @@ fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
     frame.render_widget(gauge, chunks[0]);
 
     // Counter text
-    let counter_text = format!("{} / {} objects", progress.current, progress.total);
+    let mut counter_text = format!("{} / {} objects", progress.current, progress.total);
+    if progress.in_flight > 0 {
+        counter_text.push_str(&format!(" – {} in flight", progress.in_flight));
+    }
+    if job.failed > 0 {
+        counter_text.push_str(&format!(" – {} failed", job.failed));
+    }
     let counter = Paragraph::new(counter_text)
         .style(Style::default().fg(Color::White))
         .alignment(Alignment::Center);
     frame.render_widget(counter, chunks[1]);
 
-    // Current item
-    if let Some(ref item) = progress.current_item {
+    // Current item, or why nothing new is being started
+    if job.is_cancelling() || job.is_paused() {
+        let state = if job.is_cancelling() {
+            "Cancelling"
+        } else {
+            "Paused"
+        };
+        let text = if progress.in_flight > 0 {
+            format!(
+                "{state} – waiting for {} objects in flight",
+                progress.in_flight
+            )
+        } else if job.is_paused() {
+            format!("{state} – press p to resume")
+        } else {
+            format!("{state}…")
+        };
+        let state_para = Paragraph::new(text)
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center);
+        frame.render_widget(state_para, chunks[2]);
+    } else if let Some(ref item) = progress.current_item {
         let item_text = format!("Processing: {}", item);
         let item_para = Paragraph::new(item_text)
             .style(Style::default().fg(Color::Gray))
@@ -1856,6 +3282,325 @@ fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
             .wrap(Wrap { trim: true });
         frame.render_widget(item_para, chunks[2]);
     }
+
+    // Retry and failure breakdown
+    let mut breakdown = Vec::new();
+    if job.retried > 0 {
+        breakdown.push(format!("{} objects needed retries", job.retried));
+    }
+    if !job.failures.is_empty() {
+        breakdown.push(format!("Failures – {}", job.failure_breakdown()));
+    }
+    if !breakdown.is_empty() {
+        let breakdown_para = Paragraph::new(breakdown.join("  │  "))
+            .style(Style::default().fg(Color::LightRed))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(breakdown_para, chunks[3]);
+    }
+
+    let key_style = Style::default()
+        .bg(Color::LightCyan)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+    let hints = if job.is_running() {
+        let pause_label = if job.is_paused() {
+            " resume   "
+        } else {
+            " pause   "
+        };
+        Line::from(vec![
+            Span::styled(" b ", key_style),
+            Span::raw(" run in background   "),
+            Span::styled(" p ", key_style),
+            Span::raw(pause_label),
+            Span::styled(" Esc ", key_style),
+            Span::raw(" cancel"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" Esc ", key_style),
+            Span::raw(" close   "),
+            Span::styled(" j ", key_style),
+            Span::raw(" all jobs (from browser)"),
+        ])
+    };
+    let hints = Paragraph::new(hints).alignment(Alignment::Center);
+    frame.render_widget(hints, chunks[5]);
+}
+
+fn draw_jobs_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(80, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(
+            "Jobs – ↑↓ select, Enter details, p pause/resume, c cancel, r resume cancelled, x clear finished, Esc/j close",
+        )
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    if app.jobs.is_empty() {
+        let para = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("No jobs yet."),
+            Line::from(""),
+            Line::from("Confirmed transitions and restores run here in the background."),
+        ])
+        .block(block);
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .jobs
+        .iter()
+        .map(|job| {
+            let (status_text, status_style) = match &job.status {
+                JobStatus::Running if job.is_cancelling() => {
+                    ("Cancelling".to_string(), Style::default().fg(Color::Yellow))
+                }
+                JobStatus::Running if job.is_paused() => {
+                    ("Paused".to_string(), Style::default().fg(Color::Magenta))
+                }
+                JobStatus::Running => ("Running".to_string(), Style::default().fg(Color::Yellow)),
+                JobStatus::Cancelled => (
+                    format!("Cancelled, {} not started", job.skipped.len()),
+                    Style::default().fg(Color::LightRed),
+                ),
+                JobStatus::Completed if job.failed > 0 => (
+                    format!("Done, {} failed", job.failed),
+                    Style::default().fg(Color::LightRed),
+                ),
+                JobStatus::Completed => ("Done".to_string(), Style::default().fg(Color::Green)),
+                JobStatus::Failed(err) => {
+                    (format!("Failed: {err}"), Style::default().fg(Color::Red))
+                }
+            };
+            let filled = (job.progress.percentage() / 5) as usize;
+            let bar = format!(
+                "[{}{}]",
+                "█".repeat(filled),
+                "·".repeat(20 - filled.min(20))
+            );
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("#{:<3} ", job.id),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("{:<32} ", job.progress.operation)),
+                Span::styled(
+                    format!("{:<24} ", job.bucket),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(bar, Style::default().fg(Color::Cyan)),
+                Span::raw(format!(
+                    " {:>3}% {}/{}  ",
+                    job.progress.percentage(),
+                    job.progress.current,
+                    job.progress.total
+                )),
+                Span::styled(status_text, status_style),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.job_cursor.min(app.jobs.len() - 1)));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_journals_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(80, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title("Interrupted jobs – ↑↓ select, Enter resume, d discard, Esc decide later")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    let items: Vec<ListItem> = app
+        .pending_journals
+        .iter()
+        .map(|journal| {
+            let operation = match &journal.action {
+                PendingAction::Transition { target_class } => {
+                    format!("Transition to {}", target_class.label())
+                }
+                PendingAction::Restore { days, tier } => {
+                    format!("{} restore for {days} days", tier.label())
+                }
+            };
+            let started = chrono::DateTime::parse_from_rfc3339(&journal.created_at)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|_| journal.created_at.clone());
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{started}  ")),
+                Span::raw(format!("{:<32} ", operation)),
+                Span::styled(
+                    format!("{:<24} ", journal.bucket),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!(
+                        "{} / {} done, {} remaining",
+                        journal.done_count(),
+                        journal.items.len(),
+                        journal.remaining_count()
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.journal_cursor.min(app.pending_journals.len() - 1)));
+    }
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_mask_library_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(80, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(
+            "Mask library – Enter load, e edit, s save active mask, r rename, c duplicate, d delete, Esc close",
+        )
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    if app.mask_library.masks.is_empty() {
+        let para = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(
+                "  No saved masks yet. Apply a mask with 'm', then press 's' here to save it.",
+            ),
+        ])
+        .block(block);
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let current_bucket = app.selected_bucket_name();
+    let items: Vec<ListItem> = app
+        .mask_library
+        .masks
+        .iter()
+        .map(|saved| {
+            let usable = saved.usable_in(current_bucket);
+            let name_style = if usable {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let binding = match &saved.bucket {
+                Some(bucket) => format!("bucket {bucket}"),
+                None => "any bucket".to_string(),
+            };
+            let mut detail = saved.mask.summary();
+            if !saved.description.is_empty() {
+                detail = format!("{} – {}", saved.description, detail);
+            }
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(format!("{:<32} ", saved.name), name_style),
+                    Span::styled(binding, Style::default().fg(Color::Cyan)),
+                ]),
+                Line::from(Span::styled(
+                    format!("  {detail}"),
+                    Style::default().fg(Color::Gray),
+                )),
+            ])
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.library_cursor.min(items.len() - 1)));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_library_form_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let form = &app.library_form;
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let field_line = |label: &'static str, value: String, field: LibraryFormField| {
+        let focused = form.field == field;
+        let mut spans = vec![
+            Span::styled(label, if focused { active_style } else { label_style }),
+            Span::styled(
+                value,
+                if focused {
+                    active_style
+                } else {
+                    Style::default()
+                },
+            ),
+        ];
+        if focused && field != LibraryFormField::Bucket {
+            spans.push(Span::styled(
+                " ",
+                Style::default().fg(Color::Black).bg(Color::LightYellow),
+            ));
+        }
+        Line::from(spans)
+    };
+
+    let title = if form.editing.is_some() {
+        " Rename Saved Mask "
+    } else {
+        " Save Mask to Library "
+    };
+    let block = Block::default()
+        .title(Span::styled(title, active_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+    let lines = vec![
+        Line::from(""),
+        field_line("Name:        ", form.name.clone(), LibraryFormField::Name),
+        field_line(
+            "Description: ",
+            form.description.clone(),
+            LibraryFormField::Description,
+        ),
+        field_line(
+            "Bucket:      ",
+            form.bucket
+                .clone()
+                .unwrap_or_else(|| "Any bucket".to_string()),
+            LibraryFormField::Bucket,
+        ),
+        Line::from(Span::styled(
+            "             (space binds to the selected bucket)",
+            hint_style,
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Tab move between fields  Enter save  Esc cancel",
+            hint_style,
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 fn draw_credential_error_popup(frame: &mut ratatui::Frame) {
@@ -1936,45 +3681,6 @@ fn draw_modal_surface(frame: &mut ratatui::Frame, area: Rect) {
     }
 }
 
-fn describe_restore_error(err: &anyhow::Error) -> String {
-    if let Some(sdk_err) = err.downcast_ref::<SdkError<RestoreObjectError>>() {
-        match sdk_err {
-            SdkError::ServiceError(err) => {
-                let service = err.err();
-                let code = service.meta().code().unwrap_or("ServiceError");
-                let message = service
-                    .message()
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| "no message provided".into());
-                let friendly = match code {
-                    "NoSuchKey" => {
-                        "object was not found (mask may target stale keys or bucket differs)".into()
-                    }
-                    "InvalidObjectState" => {
-                        "object is already being restored or not eligible for this operation".into()
-                    }
-                    _ => message.clone(),
-                };
-                if matches!(code, "NoSuchKey" | "InvalidObjectState") {
-                    return format!("{code}: {friendly}");
-                }
-                return format!("{code}: {message}");
-            }
-            SdkError::DispatchFailure(err) => {
-                return format!("network/dispatch failure: {err:?}");
-            }
-            SdkError::TimeoutError(_) => {
-                return "request timed out; please retry".into();
-            }
-            SdkError::ResponseError(ctx) => {
-                return format!("response error: {ctx:?}");
-            }
-            _ => {}
-        }
-    }
-    format!("{err:#}")
-}
-
 fn centered_rect(width_percent: u16, height_percent: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -2004,12 +3710,6 @@ fn highlight_border(active: bool) -> Style {
     }
 }
 
-fn format_size(size: i64) -> String {
-    const KB: f64 = 1024.0;
-    let kb = size as f64 / KB;
-    format!("{:>10.2} KB", kb)
-}
-
 fn storage_class_color(storage_class: &StorageClassTier) -> Style {
     match storage_class {
         StorageClassTier::Standard => Style::default()